@@ -0,0 +1,198 @@
+//! Benchmarks for lexicon building and move generation.
+//!
+//! The lexicon is generated from a fixed seed so the numbers are comparable between runs and
+//! machines without shipping a dictionary. Set `SCRABBLE_BENCH_LEXICON` to a word list (one
+//! word per line) to benchmark against a real one instead. Positions live in
+//! `benches/positions` in the plain-text position format; the words already on their boards
+//! are always added to the lexicon.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+
+use scrabble_move_generation::constants::BOARD_SIZE;
+use scrabble_move_generation::core::{Board, Position, Rack};
+use scrabble_move_generation::move_generation::{Gaddag, GeneratorContext, MoveGenerator};
+
+/// Number of words in the synthetic lexicon.
+const SYNTHETIC_WORDS: usize = 40_000;
+const SEED: u64 = 0x5eed_cafe_f00d_d00d;
+
+const POSITIONS: [(&str, &str); 4] = [
+    ("empty", include_str!("positions/empty.txt")),
+    ("midgame", include_str!("positions/midgame.txt")),
+    (
+        "midgame_blanks",
+        include_str!("positions/midgame_blanks.txt"),
+    ),
+    ("crowded", include_str!("positions/crowded.txt")),
+];
+
+const ONSETS: [&str; 30] = [
+    "B", "C", "D", "F", "G", "H", "J", "K", "L", "M", "N", "P", "QU", "R", "S", "T", "V", "W", "Y",
+    "Z", "BR", "CH", "CL", "DR", "GR", "PL", "SH", "ST", "TR", "TH",
+];
+const VOWELS: [&str; 12] = [
+    "A", "E", "I", "O", "U", "A", "E", "I", "O", "EA", "OU", "AI",
+];
+const CODAS: [&str; 16] = [
+    "", "", "", "N", "R", "S", "T", "L", "D", "X", "NG", "ST", "RT", "CK", "M", "SS",
+];
+
+/// xorshift64*, enough to make a repeatable word list.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn pick<'a>(&mut self, options: &[&'a str]) -> &'a str {
+        options[(self.next() % options.len() as u64) as usize]
+    }
+}
+
+/// Pronounceable words of one to three syllables, so racks find a realistic number of plays.
+fn synthetic_lexicon(count: usize) -> Vec<String> {
+    let mut rng = Rng(SEED);
+    let mut words = Vec::with_capacity(count);
+    while words.len() < count {
+        let syllables = 1 + rng.next() % 3;
+        let mut word = String::new();
+        for _ in 0..syllables {
+            if !rng.next().is_multiple_of(4) {
+                word.push_str(rng.pick(&ONSETS));
+            }
+            word.push_str(rng.pick(&VOWELS));
+            word.push_str(rng.pick(&CODAS));
+        }
+        if (2..=BOARD_SIZE).contains(&word.len()) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Every run of two or more tiles along the rows and columns of `board`.
+fn board_words(board: &Board) -> Vec<String> {
+    let mut words = Vec::new();
+    for (step, line_step) in [(1, BOARD_SIZE), (BOARD_SIZE, 1)] {
+        for line in 0..BOARD_SIZE {
+            let mut word = String::new();
+            for i in 0..BOARD_SIZE {
+                let tile = board.get(line * line_step + i * step);
+                if board.is_cell_empty(line * line_step + i * step) {
+                    if word.len() > 1 {
+                        words.push(word.clone());
+                    }
+                    word.clear();
+                } else {
+                    word.push(tile.to_ascii_uppercase());
+                }
+            }
+            if word.len() > 1 {
+                words.push(word);
+            }
+        }
+    }
+    words
+}
+
+fn positions() -> Vec<(&'static str, Position)> {
+    POSITIONS
+        .iter()
+        .map(|&(name, text)| (name, Position::from_text(text).unwrap()))
+        .collect()
+}
+
+fn lexicon(positions: &[(&str, Position)]) -> Vec<String> {
+    let mut words = match std::env::var("SCRABBLE_BENCH_LEXICON") {
+        Ok(path) => std::fs::read_to_string(path)
+            .expect("cannot read SCRABBLE_BENCH_LEXICON")
+            .lines()
+            .map(|line| line.trim().to_ascii_uppercase())
+            .filter(|word| !word.is_empty())
+            .collect(),
+        Err(_) => synthetic_lexicon(SYNTHETIC_WORDS),
+    };
+    for (_, position) in positions {
+        words.extend(board_words(&position.board));
+    }
+    words
+}
+
+fn rack(position: &Position) -> Rack {
+    position.racks[0].as_deref().unwrap_or("").parse().unwrap()
+}
+
+fn bench_build(c: &mut Criterion) {
+    let words = lexicon(&positions());
+
+    let mut group = c.benchmark_group("gaddag");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(words.len() as u64));
+    group.bench_function("from_wordlist", |b| {
+        b.iter(|| Gaddag::from_wordlist(black_box(&words)))
+    });
+    group.finish();
+}
+
+fn bench_generate(c: &mut Criterion) {
+    let positions = positions();
+    let gaddag = Gaddag::from_wordlist(&lexicon(&positions));
+    let generator = MoveGenerator::new(&gaddag);
+
+    let mut group = c.benchmark_group("generate_all_moves");
+    for (name, position) in &positions {
+        // Throughput in moves makes criterion report moves per second
+        let moves = generator.generate_all_moves(&position.board, &mut rack(position));
+        group.throughput(Throughput::Elements(moves.len() as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            position,
+            |b, position| {
+                let mut rack = rack(position);
+                b.iter(|| generator.generate_all_moves(black_box(&position.board), &mut rack))
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_cross_checks(c: &mut Criterion) {
+    let positions = positions();
+    let gaddag = Gaddag::from_wordlist(&lexicon(&positions));
+    let generator = MoveGenerator::new(&gaddag);
+
+    let mut group = c.benchmark_group("is_crossword_valid");
+    for (name, position) in positions.iter().filter(|(_, p)| !p.board.is_empty()) {
+        // Every letter on every anchor, in both directions
+        let board = &position.board;
+        let anchors: Vec<usize> = board.anchors().iter().collect();
+        let gen_ctx = GeneratorContext::new(board);
+
+        group.throughput(Throughput::Elements(anchors.len() as u64 * 26 * 2));
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter(|| {
+                let mut valid = 0;
+                for &anchor in &anchors {
+                    for tile in 'A'..='Z' {
+                        for is_horizontal in [true, false] {
+                            valid +=
+                                generator.is_crossword_valid(&gen_ctx, tile, anchor, is_horizontal)
+                                    as usize;
+                        }
+                    }
+                }
+                valid
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_build, bench_generate, bench_cross_checks);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+//! Turning fuzzer input into boards and racks.
+
+#![allow(dead_code)]
+
+use std::sync::OnceLock;
+
+use scrabble_move_generation::constants::{BLANK, BoardPosition, RACK_SIZE, TOTAL_SIZE};
+use scrabble_move_generation::core::{Board, Rack};
+use scrabble_move_generation::move_generation::Gaddag;
+
+/// A few hundred short words over a handful of letters, so arbitrary boards have real words
+/// on them and racks find plays.
+pub const WORDS: &[&str] = &[
+    "A", "AA", "AD", "AE", "AN", "AR", "AS", "AT", "DA", "DE", "ED", "EN", "ER", "ES", "ET", "NA",
+    "NE", "RE", "TA", "TE", "ADS", "AND", "ANE", "ANT", "ARE", "ART", "ATE", "DEN", "EAR", "EAT",
+    "END", "ERA", "ERN", "ETA", "NET", "RAD", "RAN", "RAT", "RED", "SAD", "SAT", "SEA", "SET",
+    "TAD", "TAN", "TAR", "TEA", "TED", "TEN", "DARE", "DART", "DATE", "DEAN", "DEAR", "EARN",
+    "NEAT", "NERD", "RANT", "READ", "REND", "RENT", "SAND", "SANE", "SEAT", "SEND", "STAR", "TEAR",
+    "TEND", "TERN", "TRADE", "TREAD", "STARE", "SNARE", "DATES", "TREND", "STERN",
+];
+
+/// The GADDAG of `WORDS`, built on first use.
+pub fn gaddag() -> &'static Gaddag {
+    static GADDAG: OnceLock<Gaddag> = OnceLock::new();
+    GADDAG.get_or_init(|| Gaddag::from_wordlist(&WORDS.iter().map(|w| w.to_string()).collect()))
+}
+
+/// Tiles from the letters of `WORDS`, with blanks (lowercase) for about one byte in eight.
+pub fn tile(byte: u8) -> char {
+    const LETTERS: &[u8] = b"ADENRST";
+    let letter = LETTERS[byte as usize % LETTERS.len()] as char;
+    if byte >= 224 {
+        letter.to_ascii_lowercase()
+    } else {
+        letter
+    }
+}
+
+/// A board with a tile for each `(square, tile)` pair, skipping squares that are taken.
+pub fn board(tiles: &[(u8, u8)]) -> Board {
+    let mut board = Board::new();
+    for &(square, byte) in tiles {
+        let pos = square as usize % TOTAL_SIZE;
+        if board.is_cell_empty(pos) {
+            board.place(tile(byte), pos as BoardPosition);
+            board.increase_tile_count();
+        }
+    }
+    board
+}
+
+/// A rack of up to `RACK_SIZE` tiles, with a blank for bytes of 240 and up.
+pub fn rack(bytes: &[u8]) -> Rack {
+    let tiles: String = bytes
+        .iter()
+        .take(RACK_SIZE)
+        .map(|&byte| {
+            if byte >= 240 {
+                BLANK
+            } else {
+                tile(byte).to_ascii_uppercase()
+            }
+        })
+        .collect();
+    tiles.parse().expect("rack tiles are valid")
+}
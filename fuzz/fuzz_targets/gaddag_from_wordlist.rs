@@ -0,0 +1,56 @@
+//! Arbitrary word lists into `Gaddag::try_from_wordlist`: invalid lists are rejected, and
+//! valid ones hold exactly their words.
+
+#![no_main]
+
+use std::collections::BTreeSet;
+
+use libfuzzer_sys::fuzz_target;
+use scrabble_move_generation::move_generation::Gaddag;
+
+fuzz_target!(|words: Vec<String>| {
+    let valid = |word: &String| !word.is_empty() && word.chars().all(|c| c.is_ascii_uppercase());
+    match Gaddag::try_from_wordlist(&words) {
+        Ok(gaddag) => check(&gaddag, &words),
+        Err(_) => assert!(!words.iter().all(valid)),
+    }
+
+    // The letters of the input, so most runs get past validation
+    let letters: Vec<String> = words
+        .iter()
+        .map(|word| {
+            word.chars()
+                .filter(char::is_ascii_alphabetic)
+                .map(|c| c.to_ascii_uppercase())
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+    check(&Gaddag::try_from_wordlist(&letters).unwrap(), &letters);
+});
+
+fn check(gaddag: &Gaddag, words: &[String]) {
+    let expected: BTreeSet<&str> = words.iter().map(String::as_str).collect();
+    let listed: Vec<String> = gaddag.words().collect();
+    assert!(
+        listed
+            .iter()
+            .map(String::as_str)
+            .eq(expected.iter().copied()),
+        "words() should list the input sorted and without duplicates"
+    );
+    assert_eq!(gaddag.word_stats().count, expected.len());
+
+    for word in &expected {
+        assert!(gaddag.is_word(word));
+        assert!(gaddag.anagrams(word).iter().any(|anagram| anagram == word));
+        // Every prefix of a word is on a path, so the longer word must still be stored alone
+        let mut extended = word.to_string();
+        extended.push('A');
+        assert_eq!(
+            gaddag.is_word(&extended),
+            expected.contains(extended.as_str())
+        );
+    }
+    assert!(!gaddag.is_word(""));
+}
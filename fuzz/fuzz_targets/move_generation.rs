@@ -0,0 +1,59 @@
+//! Arbitrary boards and racks into `MoveGenerator`. Every move must be legal and distinct,
+//! playing and taking back a move must restore the board, and small racks without blanks must
+//! find the same moves as `ReferenceGenerator`.
+
+#![no_main]
+
+mod common;
+
+use std::collections::BTreeSet;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use scrabble_move_generation::constants::BLANK;
+use scrabble_move_generation::move_generation::{MoveGenerator, ReferenceGenerator};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    tiles: Vec<(u8, u8)>,
+    rack: Vec<u8>,
+}
+
+/// Racks the brute-force reference still checks quickly, as long as they have no blank.
+const REFERENCE_RACK_SIZE: usize = 4;
+
+fuzz_target!(|input: Input| {
+    let gaddag = common::gaddag();
+    let board = common::board(&input.tiles);
+    let mut rack = common::rack(&input.rack);
+    let rack_before = rack.to_string();
+
+    let moves = MoveGenerator::new(gaddag).generate_all_moves(&board, &mut rack);
+    assert_eq!(rack.to_string(), rack_before, "the rack must be restored");
+
+    let reference = ReferenceGenerator::new(gaddag);
+    let canonical: BTreeSet<_> = moves.iter().map(ReferenceGenerator::canonical).collect();
+    assert_eq!(canonical.len(), moves.len(), "duplicate moves");
+
+    for crossword_move in &moves {
+        assert!(
+            reference.is_valid_move(&board, crossword_move),
+            "invalid move {crossword_move:?}"
+        );
+        board.score_move(crossword_move);
+
+        let mut played = board.clone();
+        played.try_make_move(crossword_move).unwrap();
+        played.try_undo_move(crossword_move).unwrap();
+        assert_eq!(played, board, "undo_move must restore the board");
+    }
+
+    if rack.len <= REFERENCE_RACK_SIZE && !rack.contains(BLANK) {
+        let expected: BTreeSet<_> = reference
+            .generate_all_moves(&board, &rack)
+            .iter()
+            .map(ReferenceGenerator::canonical)
+            .collect();
+        assert_eq!(canonical, expected);
+    }
+});
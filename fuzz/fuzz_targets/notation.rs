@@ -0,0 +1,35 @@
+//! Arbitrary notation strings into `CrosswordMove::from_notation`. Whatever parses must be
+//! playable on the board and come back unchanged through `to_notation`.
+
+#![no_main]
+
+mod common;
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use scrabble_move_generation::core::CrosswordMove;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    tiles: Vec<(u8, u8)>,
+    notation: String,
+}
+
+fuzz_target!(|input: Input| {
+    let board = common::board(&input.tiles);
+    let Ok(crossword_move) = CrosswordMove::from_notation(&input.notation, &board) else {
+        return;
+    };
+
+    let written = crossword_move.to_notation(&board);
+    assert_eq!(
+        CrosswordMove::from_notation(&written, &board).unwrap(),
+        crossword_move,
+        "{written} should parse back to the same move"
+    );
+
+    let mut played = board.clone();
+    played.try_make_move(&crossword_move).unwrap();
+    played.try_undo_move(&crossword_move).unwrap();
+    assert_eq!(played, board);
+});
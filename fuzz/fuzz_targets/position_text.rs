@@ -0,0 +1,19 @@
+//! Arbitrary text into `Position::from_text`. Whatever parses must be written back by
+//! `to_text` in a form that parses to the same position.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scrabble_move_generation::core::Position;
+
+fuzz_target!(|text: &str| {
+    let Ok(position) = Position::from_text(text) else {
+        return;
+    };
+    let written = position.to_text();
+    assert_eq!(
+        Position::from_text(&written).unwrap(),
+        position,
+        "{written}"
+    );
+});
@@ -0,0 +1,137 @@
+//! Lexicon tools working on plain word lists, see `USAGE`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+
+use scrabble_move_generation::move_generation::Gaddag;
+
+const USAGE: &str = "\
+Usage: lexicon <COMMAND>
+
+Every command writes to OUTPUT if given, otherwise to standard output.
+
+Commands:
+  stats <WORDLIST> [OUTPUT]     Word count and number of words of each length
+  hooks <WORDLIST> [OUTPUT]     Front hooks, word and back hooks for every word, tab
+                                separated
+  diff <OLD> <NEW> [OUTPUT]     +WORD for every added and -WORD for every removed word
+  union <A> <B> [OUTPUT]        Words in A or B
+  intersection <A> <B> [OUTPUT] Words in both A and B
+  difference <A> <B> [OUTPUT]   Words in A but not in B
+  symmetric-difference <A> <B> [OUTPUT]
+                                Words in exactly one of A and B
+  merge <A> <B> [OUTPUT]        Words in A or B with their sources: 1, 2 or 1,2";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let result = match args.as_slice() {
+        ["-h" | "--help"] => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        ["stats", wordlist, output @ ..] if output.len() <= 1 => {
+            stats(wordlist, output.first().copied())
+        }
+        ["hooks", wordlist, output @ ..] if output.len() <= 1 => {
+            hooks(wordlist, output.first().copied())
+        }
+        [command, a, b, output @ ..] if output.len() <= 1 => {
+            compare(command, a, b, output.first().copied())
+        }
+        _ => Err(String::new()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            if message.is_empty() {
+                eprintln!("{USAGE}");
+            } else {
+                eprintln!("error: {message}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn load(path: &str) -> Result<Gaddag, String> {
+    Gaddag::from_file(path).map_err(|e| format!("cannot load lexicon {path}: {e}"))
+}
+
+fn open_output(output: Option<&str>) -> Result<Box<dyn Write>, String> {
+    Ok(match output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).map_err(|e| format!("cannot create {path}: {e}"))?,
+        )),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    })
+}
+
+fn stats(wordlist: &str, output: Option<&str>) -> Result<(), String> {
+    let stats = load(wordlist)?.word_stats();
+    let mut out = open_output(output)?;
+
+    let mut write = || -> io::Result<()> {
+        writeln!(out, "words\t{}", stats.count)?;
+        writeln!(out, "mean length\t{:.2}", stats.mean_length())?;
+        for (length, &count) in stats.by_length.iter().enumerate() {
+            if count > 0 {
+                writeln!(out, "length {length}\t{count}")?;
+            }
+        }
+        out.flush()
+    };
+    write().map_err(|e| format!("cannot write stats: {e}"))
+}
+
+fn hooks(wordlist: &str, output: Option<&str>) -> Result<(), String> {
+    let gaddag = load(wordlist)?;
+    let mut out = open_output(output)?;
+
+    gaddag
+        .write_hooks_table(&mut out)
+        .and_then(|()| out.flush())
+        .map_err(|e| format!("cannot write hooks table: {e}"))
+}
+
+fn compare(command: &str, a: &str, b: &str, output: Option<&str>) -> Result<(), String> {
+    if !matches!(
+        command,
+        "diff" | "union" | "intersection" | "difference" | "symmetric-difference" | "merge"
+    ) {
+        return Err(format!("unknown command '{command}'"));
+    }
+
+    let (a, b) = (load(a)?, load(b)?);
+    let mut out = open_output(output)?;
+
+    let written = match command {
+        "diff" => a.diff(&b).write_changes(&mut out),
+        "merge" => Gaddag::merged(&[&a, &b])
+            .words_with_sources()
+            .iter()
+            .try_for_each(|(word, sources)| {
+                let sources = match sources {
+                    0b01 => "1",
+                    0b10 => "2",
+                    _ => "1,2",
+                };
+                writeln!(out, "{word}\t{sources}")
+            }),
+        _ => {
+            let words = match command {
+                "union" => a.union(&b),
+                "intersection" => a.intersection(&b),
+                "difference" => a.difference(&b),
+                _ => a.symmetric_difference(&b),
+            };
+            words.iter().try_for_each(|word| writeln!(out, "{word}"))
+        }
+    };
+
+    written
+        .and_then(|()| out.flush())
+        .map_err(|e| format!("cannot write {command} output: {e}"))
+}
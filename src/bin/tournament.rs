@@ -0,0 +1,288 @@
+//! Self-play between two bots, see `USAGE`.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use scrabble_move_generation::evaluation::{InferenceConfig, SimulationConfig};
+use scrabble_move_generation::game::{
+    AnalysisConfig, Bot, CasualPlayer, Difficulty, GameRecord, Player, Rng, TournamentConfig,
+    analyse, run_tournament_with, summarize, write_csv,
+};
+use scrabble_move_generation::move_generation::Gaddag;
+
+const USAGE: &str = "\
+Usage: tournament <WORDLIST> <BOT_A> <BOT_B> [OPTIONS]
+
+Plays BOT_A against BOT_B and prints BOT_A's win rate and mean spread with 95% confidence
+intervals. Bots take turns going first.
+
+Bots:
+  random                  Random legal move
+  score                   Highest-scoring move
+  equity                  Best score plus leave value
+  sim[:CANDIDATESxITERATIONS][+infer]
+                          Best of the top moves by equity against simulated replies
+                          (default sim:8x32); with +infer, opponent racks follow the
+                          leaves their last play suggests
+  weak[:PERCENT:RANK]     Best move by equity, except for PERCENT% of the turns where it
+                          plays the 2nd- to RANK-th-best move (default weak:25:5)
+  casual[:LEVEL]          Mix of score and randomness that plays at most a few tiles,
+                          from level 1 (beginner) to 5 (highest score; default 3); only
+                          plays words in --vocabulary, if given
+
+Options:
+  --games <N>             Number of games (default 100)
+  --seed <SEED>           Seed of the tournament (default 0)
+  --threads <N>           Worker threads (default: available cores)
+  --csv <FILE>            Write one line per game to FILE
+  --gcg <DIR>             Write every game to DIR/game_<N>.gcg
+  --analysis <DIR>        Compare every turn with the best play by equity and write the
+                          reports to DIR/game_<N>.txt, .json and annotated .gcg
+  --blunder <POINTS>      Equity lost that makes a blunder in the analysis (default 10)
+  --vocabulary <FILE>     Words casual bots may play, one per line, most common first;
+                          moves are still checked against WORDLIST
+  --rank <N>              Only the first N words of the vocabulary";
+
+struct Options {
+    wordlist: String,
+    bots: [Bot; 2],
+    config: TournamentConfig,
+    csv: Option<String>,
+    gcg: Option<String>,
+    analysis: Option<String>,
+    analysis_config: AnalysisConfig,
+    vocabulary: Option<String>,
+    rank: Option<usize>,
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if matches!(args.as_slice(), [flag] if flag == "-h" || flag == "--help") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(&args).and_then(|options| run(&options)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            if message.is_empty() {
+                eprintln!("{USAGE}");
+            } else {
+                eprintln!("error: {message}");
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let [wordlist, a, b, rest @ ..] = args else {
+        return Err(String::new());
+    };
+    let mut options = Options {
+        wordlist: wordlist.clone(),
+        bots: [parse_bot(a)?, parse_bot(b)?],
+        config: TournamentConfig::default(),
+        csv: None,
+        gcg: None,
+        analysis: None,
+        analysis_config: AnalysisConfig::default(),
+        vocabulary: None,
+        rank: None,
+    };
+
+    let mut has_blunder = false;
+    let mut rest = rest.iter();
+    while let Some(flag) = rest.next() {
+        let value = rest.next().ok_or_else(|| format!("{flag} needs a value"))?;
+        let number = || {
+            value
+                .parse::<u64>()
+                .map_err(|_| format!("invalid value '{value}' for {flag}"))
+        };
+        match flag.as_str() {
+            "--games" => options.config.games = number()? as usize,
+            "--seed" => options.config.seed = number()?,
+            "--threads" => options.config.threads = number()?.max(1) as usize,
+            "--csv" => options.csv = Some(value.clone()),
+            "--gcg" => options.gcg = Some(value.clone()),
+            "--analysis" => options.analysis = Some(value.clone()),
+            "--blunder" => {
+                options.analysis_config.blunder_threshold = value
+                    .parse()
+                    .ok()
+                    .filter(|&points: &f32| points.is_finite() && points > 0.0)
+                    .ok_or_else(|| format!("invalid value '{value}' for {flag}"))?;
+                has_blunder = true;
+            }
+            "--vocabulary" => options.vocabulary = Some(value.clone()),
+            "--rank" => options.rank = Some(number()? as usize),
+            _ => return Err(format!("unknown option '{flag}'")),
+        }
+    }
+
+    if has_blunder && options.analysis.is_none() {
+        return Err("--blunder needs --analysis".to_string());
+    }
+    if options.rank.is_some() && options.vocabulary.is_none() {
+        return Err("--rank needs --vocabulary".to_string());
+    }
+    let has_casual = options.bots.iter().any(|bot| matches!(bot, Bot::Casual(_)));
+    if options.vocabulary.is_some() && !has_casual {
+        return Err("--vocabulary needs a casual bot".to_string());
+    }
+    Ok(options)
+}
+
+fn parse_bot(name: &str) -> Result<Bot, String> {
+    let invalid = || format!("unknown bot '{name}'");
+    if let Some(sim) = name.strip_suffix("+infer") {
+        return match parse_bot(sim) {
+            Ok(Bot::Simulation(config)) => Ok(Bot::Simulation(SimulationConfig {
+                inference: Some(InferenceConfig::default()),
+                ..config
+            })),
+            _ => Err(invalid()),
+        };
+    }
+    Ok(match name.split_once(':') {
+        None if name == "random" => Bot::Random,
+        None if name == "score" => Bot::HighestScore,
+        None if name == "equity" => Bot::Equity,
+        None if name == "sim" => Bot::Simulation(SimulationConfig::default()),
+        Some(("sim", size)) => {
+            let (candidates, iterations) = size.split_once('x').ok_or_else(invalid)?;
+            Bot::Simulation(SimulationConfig {
+                candidates: candidates.parse().map_err(|_| invalid())?,
+                iterations: iterations.parse().map_err(|_| invalid())?,
+                inference: None,
+            })
+        }
+        None if name == "weak" => Bot::Weakened {
+            mistake_rate: 0.25,
+            max_rank: 5,
+        },
+        Some(("weak", settings)) => {
+            let (percent, rank) = settings.split_once(':').ok_or_else(invalid)?;
+            let percent: f64 = percent.parse().map_err(|_| invalid())?;
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(invalid());
+            }
+            Bot::Weakened {
+                mistake_rate: percent / 100.0,
+                max_rank: rank.parse().map_err(|_| invalid())?,
+            }
+        }
+        None if name == "casual" => Bot::Casual(Difficulty::level(3)),
+        Some(("casual", level)) => match level.parse() {
+            Ok(level @ 1..=5) => Bot::Casual(Difficulty::level(level)),
+            _ => return Err(invalid()),
+        },
+        _ => return Err(invalid()),
+    })
+}
+
+fn run(options: &Options) -> Result<(), String> {
+    let gaddag = Gaddag::from_file(&options.wordlist)
+        .map_err(|e| format!("cannot load lexicon {}: {e}", options.wordlist))?;
+
+    let vocabulary = options
+        .vocabulary
+        .as_deref()
+        .map(|path| load_vocabulary(path, options.rank))
+        .transpose()?;
+
+    let records = run_tournament_with(&options.config, |seed| {
+        std::array::from_fn(|player| -> Box<dyn Player + '_> {
+            let seed = Rng::derive_seed(seed, player as u64 + 1);
+            match (options.bots[player], &vocabulary) {
+                (Bot::Casual(difficulty), Some(vocabulary)) => Box::new(
+                    CasualPlayer::new(&gaddag, difficulty, seed).with_vocabulary(vocabulary),
+                ),
+                (bot, _) => bot.player(&gaddag, seed),
+            }
+        })
+    });
+    if let Some(record) = records.first() {
+        println!("{} vs {}", record.players[0], record.players[1]);
+    }
+    println!("{}", summarize(&records));
+
+    if let Some(path) = &options.csv {
+        let mut out = create(Path::new(path))?;
+        write_csv(&records, &mut out)
+            .and_then(|()| out.flush())
+            .map_err(|e| format!("cannot write {path}: {e}"))?;
+    }
+    if let Some(dir) = &options.gcg {
+        write_gcg_files(Path::new(dir), &records)?;
+    }
+    if let Some(dir) = &options.analysis {
+        write_analyses(Path::new(dir), &gaddag, &options.analysis_config, &records)?;
+    }
+    Ok(())
+}
+
+/// The first `rank` words of a frequency-ordered word list, or all of them.
+fn load_vocabulary(path: &str, rank: Option<usize>) -> Result<Gaddag, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("cannot read vocabulary {path}: {e}"))?;
+    let words: Vec<String> = contents
+        .lines()
+        .map(|line| line.trim().to_ascii_uppercase())
+        .filter(|word| !word.is_empty())
+        .take(rank.unwrap_or(usize::MAX))
+        .collect();
+    Gaddag::try_from_wordlist(&words).map_err(|e| format!("invalid vocabulary {path}: {e}"))
+}
+
+fn write_gcg_files(dir: &Path, records: &[GameRecord]) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+
+    for record in records {
+        let names = [record.players[0].as_str(), record.players[1].as_str()];
+        let path = dir.join(format!("game_{:04}.gcg", record.index));
+        let mut out = create(&path)?;
+        record
+            .game
+            .write_gcg(names, &mut out)
+            .and_then(|()| out.flush())
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn write_analyses(
+    dir: &Path,
+    gaddag: &Gaddag,
+    config: &AnalysisConfig,
+    records: &[GameRecord],
+) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|e| format!("cannot create {}: {e}", dir.display()))?;
+
+    for record in records {
+        let names = [record.players[0].as_str(), record.players[1].as_str()];
+        let analysis = analyse(gaddag, &record.game, config);
+        let base = dir.join(format!("game_{:04}", record.index));
+        for extension in ["txt", "json", "gcg"] {
+            let path = base.with_extension(extension);
+            let mut out = create(&path)?;
+            match extension {
+                "txt" => analysis.write_text(names, &mut out),
+                "json" => analysis.write_json(names, &mut out),
+                _ => analysis.write_gcg(&record.game, names, &mut out),
+            }
+            .and_then(|()| out.flush())
+            .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn create(path: &Path) -> Result<BufWriter<File>, String> {
+    File::create(path)
+        .map(BufWriter::new)
+        .map_err(|e| format!("cannot create {}: {e}", path.display()))
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+
+use scrabble_move_generation::constants::EMPTY_TILE;
+use scrabble_move_generation::core::{Board, Position};
+
+/// Read a position file: a board in the text format, optionally followed by metadata such
+/// as the racks and the player to move.
+pub fn load_position_file(path: &Path) -> Result<Position, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("cannot read board {}: {e}", path.display()))?;
+    Position::from_text(&contents).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Parse a compact board: rows separated by `/`, where a number stands for that many empty
+/// squares, e.g. `15/15/15/15/15/15/15/5HELLO5/15/15/15/15/15/15/15`.
+pub fn parse_compact(position: &str) -> Result<Board, String> {
+    let mut text = String::new();
+    for compact_row in position.split('/') {
+        let mut run = 0;
+        for c in compact_row.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                run = run * 10 + digit as usize;
+                continue;
+            }
+            text.extend(std::iter::repeat_n(EMPTY_TILE, run));
+            run = 0;
+            text.push(c);
+        }
+        text.extend(std::iter::repeat_n(EMPTY_TILE, run));
+        text.push('\n');
+    }
+
+    // Rows of the compact string map one-to-one onto lines of the text format
+    Board::from_text(&text)
+        .map_err(|e| format!("position row {}, column {}: {}", e.line, e.column, e.kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrabble_move_generation::constants::BOARD_SIZE;
+
+    #[test]
+    fn compact_board_expands_empty_runs() {
+        let board = parse_compact("15/15/15/15/15/15/15/5HELlO5/15/15/15/15/15/15/15").unwrap();
+
+        assert!(!board.is_empty());
+        assert_eq!(board.get(7 * BOARD_SIZE + 5), 'H');
+        assert_eq!(board.get(7 * BOARD_SIZE + 8), 'l');
+        assert!(board.is_cell_empty(7 * BOARD_SIZE + 10));
+    }
+
+    #[test]
+    fn malformed_boards_are_rejected() {
+        assert!(parse_compact("15/15").is_err());
+        assert!(parse_compact(&["14"; BOARD_SIZE].join("/")).is_err());
+        assert!(
+            parse_compact(&["15"; BOARD_SIZE].join("/"))
+                .unwrap()
+                .is_empty()
+        );
+
+        let err = parse_compact(&["7*7"; BOARD_SIZE].join("/")).unwrap_err();
+        assert_eq!(err, "position row 1, column 8: invalid tile '*'");
+    }
+}
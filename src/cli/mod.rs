@@ -0,0 +1,268 @@
+mod board_input;
+mod output;
+
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use scrabble_move_generation::core::{Board, Position, Rack};
+use scrabble_move_generation::evaluation::{EvaluatedMove, evaluate_moves};
+use scrabble_move_generation::move_generation::{Gaddag, MoveGenerator};
+use scrabble_move_generation::render::TerminalRenderer;
+
+pub const USAGE: &str = "\
+Usage: scrabble_move_generation --lexicon <PATH> [--rack <TILES>] [OPTIONS]
+
+Find the best plays for a rack on a given board.
+
+Options:
+  -l, --lexicon <PATH>     Word list with one word per line
+  -r, --rack <TILES>       Rack tiles, e.g. AEIRST? ('?' is a blank)
+  -b, --board <PATH>       Position file: 15 rows of 15 tiles, '.' for empty squares,
+                           optionally followed by metadata such as 'rack1: AEIRST?'
+  -p, --position <STRING>  Compact board: rows separated by '/', digits for empty runs
+  -n, --top <N>            Number of moves to print [default: 10]
+  -s, --sort <KEY>         Sort by score, equity or length [default: equity]
+      --json               Print the moves as JSON
+      --show-board         Draw the board with the top move played
+      --no-color           Draw the board in plain ASCII even on a terminal
+  -h, --help               Print this help
+
+Without --board or --position the board is empty. Without --rack the rack of the
+player to move is taken from the position file.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Score,
+    Equity,
+    /// Number of tiles placed from the rack.
+    Length,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BoardSource {
+    Empty,
+    File(PathBuf),
+    Compact(String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Options {
+    pub lexicon: PathBuf,
+    pub board: BoardSource,
+    pub rack: Option<String>,
+    pub top: usize,
+    pub sort: SortKey,
+    pub json: bool,
+    pub show_board: bool,
+    pub no_color: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(Options),
+    Help,
+}
+
+impl Command {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut lexicon = None;
+        let mut board = BoardSource::Empty;
+        let mut rack = None;
+        let mut top = 10;
+        let mut sort = SortKey::Equity;
+        let mut json = false;
+        let mut show_board = false;
+        let mut no_color = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("missing value for {name}"))
+            };
+
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(Command::Help),
+                "-l" | "--lexicon" => lexicon = Some(PathBuf::from(value(&arg)?)),
+                "-r" | "--rack" => rack = Some(value(&arg)?),
+                "-b" | "--board" | "-p" | "--position" => {
+                    if board != BoardSource::Empty {
+                        return Err("only one of --board and --position may be given".into());
+                    }
+                    let source = value(&arg)?;
+                    board = if arg == "-b" || arg == "--board" {
+                        BoardSource::File(PathBuf::from(source))
+                    } else {
+                        BoardSource::Compact(source)
+                    };
+                }
+                "-n" | "--top" => {
+                    let n = value(&arg)?;
+                    top = n
+                        .parse()
+                        .map_err(|_| format!("invalid number of moves '{n}'"))?;
+                }
+                "-s" | "--sort" => {
+                    sort = match value(&arg)?.as_str() {
+                        "score" => SortKey::Score,
+                        "equity" => SortKey::Equity,
+                        "length" => SortKey::Length,
+                        other => return Err(format!("unknown sort key '{other}'")),
+                    };
+                }
+                "--json" => json = true,
+                "--show-board" => show_board = true,
+                "--no-color" => no_color = true,
+                other => return Err(format!("unexpected argument '{other}'")),
+            }
+        }
+
+        if json && show_board {
+            return Err("--show-board cannot be combined with --json".into());
+        }
+
+        Ok(Command::Run(Options {
+            lexicon: lexicon.ok_or("--lexicon is required")?,
+            board,
+            rack,
+            top,
+            sort,
+            json,
+            show_board,
+            no_color,
+        }))
+    }
+}
+
+pub fn run(options: &Options) -> Result<(), String> {
+    let gaddag = Gaddag::from_file(&options.lexicon)
+        .map_err(|e| format!("cannot load lexicon {}: {e}", options.lexicon.display()))?;
+
+    let position = match &options.board {
+        BoardSource::Empty => Position::default(),
+        BoardSource::File(path) => board_input::load_position_file(path)?,
+        BoardSource::Compact(compact) => Position {
+            board: board_input::parse_compact(compact)?,
+            ..Position::default()
+        },
+    };
+    let board = position.board;
+
+    let rack = match &options.rack {
+        Some(rack) => rack,
+        None => position.racks[position.to_move.unwrap_or(0)]
+            .as_ref()
+            .ok_or("--rack is required unless the position gives the rack to move")?,
+    };
+    let mut rack = parse_rack(rack)?;
+
+    let generator = MoveGenerator::new(&gaddag);
+    let moves = generator.generate_all_moves(&board, &mut rack);
+
+    let mut evaluated = evaluate_moves(&board, &rack, moves);
+    sort_moves(&mut evaluated, options.sort, &board);
+    evaluated.truncate(options.top);
+
+    if options.json {
+        println!("{}", output::to_json(&evaluated, &board));
+        return Ok(());
+    }
+
+    if options.show_board {
+        let color = !options.no_color
+            && std::io::stdout().is_terminal()
+            && std::env::var_os("NO_COLOR").is_none();
+        let renderer = TerminalRenderer { color };
+
+        let mut shown = board.clone();
+        let best = evaluated.first().map(|m| &m.crossword_move);
+        if let Some(best) = best {
+            shown.make_move(best);
+        }
+        println!("{}", renderer.render(&shown, best));
+    }
+    print!("{}", output::to_table(&evaluated, &board));
+
+    Ok(())
+}
+
+fn parse_rack(rack: &str) -> Result<Rack, String> {
+    rack.parse()
+        .map_err(|e| format!("invalid rack '{rack}': {e}"))
+}
+
+fn sort_moves(moves: &mut [EvaluatedMove], key: SortKey, board: &Board) {
+    // Notation as the final tie-break keeps the output stable between runs
+    moves.sort_by_cached_key(|m| m.crossword_move.to_notation(board));
+    moves.sort_by(|a, b| match key {
+        SortKey::Score => b.score.cmp(&a.score).then(b.equity.total_cmp(&a.equity)),
+        SortKey::Equity => b.equity.total_cmp(&a.equity),
+        SortKey::Length => b
+            .crossword_move
+            .len()
+            .cmp(&a.crossword_move.len())
+            .then(b.score.cmp(&a.score)),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        Command::parse(args.iter().map(|s| s.to_string()))
+    }
+
+    #[test]
+    fn parses_full_command_line() {
+        let command = parse(&[
+            "-l",
+            "words.txt",
+            "-r",
+            "aeirst?",
+            "-p",
+            "15/15",
+            "-n",
+            "3",
+            "-s",
+            "score",
+            "--json",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            command,
+            Command::Run(Options {
+                lexicon: PathBuf::from("words.txt"),
+                board: BoardSource::Compact("15/15".into()),
+                rack: Some("aeirst?".into()),
+                top: 3,
+                sort: SortKey::Score,
+                json: true,
+                show_board: false,
+                no_color: false,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(parse(&["-r", "ABC"]).is_err());
+        assert!(parse(&["-l", "words.txt"]).is_ok());
+        assert!(parse(&["-l", "w", "-r", "A", "-s", "fun"]).is_err());
+        assert!(parse(&["-l", "w", "-r", "A", "-b", "x", "-p", "y"]).is_err());
+        assert!(parse(&["-l"]).is_err());
+        assert!(parse(&["-l", "w", "--json", "--show-board"]).is_err());
+        assert_eq!(parse(&["--help"]), Ok(Command::Help));
+    }
+
+    #[test]
+    fn parses_racks() {
+        let rack = parse_rack("ae?").unwrap();
+        let tiles: Vec<char> = rack.available_tiles().map(|(_, t)| t).collect();
+        assert_eq!(tiles, vec!['A', 'E', '?']);
+
+        assert!(parse_rack("ABCDEFGH").is_err());
+        assert!(parse_rack("A1").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use std::fmt::Write;
+
+use scrabble_move_generation::core::Board;
+use scrabble_move_generation::evaluation::EvaluatedMove;
+use scrabble_move_generation::render::escape_json;
+
+pub fn to_table(moves: &[EvaluatedMove], board: &Board) -> String {
+    let mut out = String::new();
+    if moves.is_empty() {
+        out.push_str("No moves found.\n");
+        return out;
+    }
+
+    writeln!(
+        out,
+        "{:>3}  {:<20} {:>5}  {:<7} {:>7}",
+        "#", "Move", "Score", "Leave", "Equity"
+    )
+    .unwrap();
+    for (rank, m) in moves.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>3}  {:<20} {:>5}  {:<7} {:>7.1}",
+            rank + 1,
+            m.crossword_move.to_notation(board),
+            m.score,
+            m.leave.iter().collect::<String>(),
+            m.equity
+        )
+        .unwrap();
+    }
+    out
+}
+
+pub fn to_json(moves: &[EvaluatedMove], board: &Board) -> String {
+    let entries: Vec<String> = moves
+        .iter()
+        .map(|m| {
+            format!(
+                "{{\"move\":\"{}\",\"score\":{},\"leave\":\"{}\",\"equity\":{:.1}}}",
+                escape_json(&m.crossword_move.to_notation(board)),
+                m.score,
+                escape_json(&m.leave.iter().collect::<String>()),
+                m.equity
+            )
+        })
+        .collect();
+    format!("[{}]", entries.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_results() {
+        let board = Board::new();
+        assert_eq!(to_json(&[], &board), "[]");
+        assert_eq!(to_table(&[], &board), "No moves found.\n");
+    }
+}
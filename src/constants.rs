@@ -1,5 +1,7 @@
 #![allow(dead_code)]
 
+use crate::error::Error;
+
 #[derive(Debug, Clone, Copy)]
 pub struct LetterData {
     pub count: u32,
@@ -61,13 +63,33 @@ pub const CHAR_TO_INDEX_TABLE: [usize; 128] = {
     table
 };
 
+/// Index of `letter` in `INDEX_TO_CHAR`, ignoring case.
+///
+/// # Panics
+/// If `letter` is not a tile; see `try_get_index`.
 pub fn get_index(letter: char) -> usize {
+    match try_get_index(letter) {
+        Ok(index) => index,
+        Err(_) => panic!("Invalid tile: {}", letter),
+    }
+}
+
+/// Index of `letter` in `INDEX_TO_CHAR`, ignoring case, or an error if it is not a tile.
+pub fn try_get_index(letter: char) -> Result<usize, Error> {
     let upper = letter.to_ascii_uppercase() as usize;
-    let index = CHAR_TO_INDEX_TABLE[upper];
-    if index >= UNIQUE_TILES {
-        panic!("Invalid tile: {}", letter);
+    match CHAR_TO_INDEX_TABLE.get(upper) {
+        Some(&index) if index < UNIQUE_TILES => Ok(index),
+        _ => Err(Error::InvalidTile(letter)),
+    }
+}
+
+/// Points for a tile as it lies on the board. Blanks are stored as lowercase letters and
+/// score nothing.
+pub fn tile_score(tile: char) -> u32 {
+    if tile.is_ascii_lowercase() {
+        return 0;
     }
-    index
+    TILE_DATA.get(&tile).map_or(0, |data| data.score)
 }
 
 pub fn is_valid_letter(letter: char) -> bool {
@@ -105,6 +127,7 @@ pub const TILE_BONUSES: [u8; TOTAL_SIZE] = [
 // -------------------------
 pub const BINGO_BONUS: u32 = 50;
 pub const RACK_SIZE: usize = 7;
+pub const PLAYER_COUNT: usize = 2;
 
 // -------------------------
 // Engine constants
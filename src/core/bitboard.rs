@@ -0,0 +1,260 @@
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::constants::BOARD_SIZE;
+
+/// Largest board a `Bitboard` can hold, 21x21 as in Super Scrabble.
+pub const MAX_BOARD_SIZE: usize = 21;
+const WORDS: usize = (MAX_BOARD_SIZE * MAX_BOARD_SIZE).div_ceil(64);
+
+/// One bit per square of a `SIZE` x `SIZE` board, row by row, as in `Board` indices.
+///
+/// Neighbours and anchors come from shifting whole boards: one square left or right is a
+/// shift by one with the column that would wrap around masked off, one square up or down
+/// a shift by `SIZE`. Bits past the last square are always zero.
+///
+/// The words are sized for `MAX_BOARD_SIZE` whatever `SIZE` is, as stable Rust cannot size
+/// an array from an expression on a const parameter; operations only touch the words the
+/// board uses, 4 of the 7 for 15x15.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Bitboard<const SIZE: usize = BOARD_SIZE> {
+    words: [u64; WORDS],
+}
+
+impl<const SIZE: usize> Default for Bitboard<SIZE> {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+impl<const SIZE: usize> Bitboard<SIZE> {
+    pub const SQUARES: usize = {
+        assert!(
+            SIZE > 0 && SIZE <= MAX_BOARD_SIZE,
+            "board size not supported"
+        );
+        SIZE * SIZE
+    };
+    /// Words holding the board's squares; the rest stay zero.
+    const USED: usize = Self::SQUARES.div_ceil(64);
+    pub const EMPTY: Self = Self { words: [0; WORDS] };
+    /// Every square of the board.
+    pub const FULL: Self = Self::columns(0, SIZE);
+    const NOT_FIRST_COLUMN: Self = Self::columns(1, SIZE);
+    const NOT_LAST_COLUMN: Self = Self::columns(0, SIZE - 1);
+
+    /// The squares of columns `from..to` on every row.
+    const fn columns(from: usize, to: usize) -> Self {
+        let mut words = [0; WORDS];
+        let mut index = 0;
+        while index < Self::SQUARES {
+            let col = index % SIZE;
+            if col >= from && col < to {
+                words[index / 64] |= 1 << (index % 64);
+            }
+            index += 1;
+        }
+        Self { words }
+    }
+
+    /// Just the square `index`.
+    pub fn square(index: usize) -> Self {
+        let mut board = Self::EMPTY;
+        board.set(index);
+        board
+    }
+
+    #[inline]
+    pub fn get(&self, index: usize) -> bool {
+        debug_assert!(index < Self::SQUARES);
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    #[inline]
+    pub fn set(&mut self, index: usize) {
+        debug_assert!(index < Self::SQUARES);
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    #[inline]
+    pub fn clear(&mut self, index: usize) {
+        debug_assert!(index < Self::SQUARES);
+        self.words[index / 64] &= !(1 << (index % 64));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.used().iter().all(|&word| word == 0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.used()
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The set squares in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.used().iter().enumerate().flat_map(|(i, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                if rest == 0 {
+                    return None;
+                }
+                let bit = rest.trailing_zeros() as usize;
+                rest &= rest - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    fn used(&self) -> &[u64] {
+        &self.words[..Self::USED]
+    }
+
+    /// Every square moved `n` squares towards higher indices; squares moved past the end
+    /// of the board are dropped.
+    fn shift_up_by(self, n: usize) -> Self {
+        debug_assert!(n > 0 && n < 64);
+        let mut words = [0; WORDS];
+        let mut carry = 0;
+        for (shifted, &word) in words.iter_mut().zip(self.used()) {
+            *shifted = word << n | carry;
+            carry = word >> (64 - n);
+        }
+        Self { words } & Self::FULL
+    }
+
+    /// Every square moved `n` squares towards lower indices.
+    fn shift_down_by(self, n: usize) -> Self {
+        debug_assert!(n > 0 && n < 64);
+        let mut words = [0; WORDS];
+        let mut carry = 0;
+        for (shifted, &word) in words.iter_mut().zip(self.used()).rev() {
+            *shifted = word >> n | carry;
+            carry = word << (64 - n);
+        }
+        Self { words }
+    }
+
+    /// The squares to the right of the set ones.
+    pub fn right(self) -> Self {
+        self.shift_up_by(1) & Self::NOT_FIRST_COLUMN
+    }
+
+    pub fn left(self) -> Self {
+        self.shift_down_by(1) & Self::NOT_LAST_COLUMN
+    }
+
+    /// The squares below the set ones.
+    pub fn down(self) -> Self {
+        self.shift_up_by(SIZE)
+    }
+
+    pub fn up(self) -> Self {
+        self.shift_down_by(SIZE)
+    }
+
+    /// The squares next to a set one, left, right, up or down; they may be set themselves.
+    pub fn neighbours(self) -> Self {
+        self.left() | self.right() | self.up() | self.down()
+    }
+}
+
+impl<const SIZE: usize> BitAnd for Bitboard<SIZE> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Self {
+            words: std::array::from_fn(|i| self.words[i] & other.words[i]),
+        }
+    }
+}
+
+impl<const SIZE: usize> BitOr for Bitboard<SIZE> {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            words: std::array::from_fn(|i| self.words[i] | other.words[i]),
+        }
+    }
+}
+
+/// The other squares of the board.
+impl<const SIZE: usize> Not for Bitboard<SIZE> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self {
+            words: std::array::from_fn(|i| !self.words[i] & Self::FULL.words[i]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Squares next to `index` by row and column arithmetic.
+    fn neighbours(size: usize, index: usize) -> Vec<usize> {
+        let (row, col) = (index / size, index % size);
+        let mut squares = Vec::new();
+        if row > 0 {
+            squares.push(index - size);
+        }
+        if col > 0 {
+            squares.push(index - 1);
+        }
+        if col + 1 < size {
+            squares.push(index + 1);
+        }
+        if row + 1 < size {
+            squares.push(index + size);
+        }
+        squares
+    }
+
+    fn check_neighbours<const SIZE: usize>(squares: &[usize]) {
+        let mut board = Bitboard::<SIZE>::EMPTY;
+        for &index in squares {
+            board.set(index % Bitboard::<SIZE>::SQUARES);
+        }
+        let mut expected: Vec<usize> = board
+            .iter()
+            .flat_map(|index| neighbours(SIZE, index))
+            .collect();
+        expected.sort_unstable();
+        expected.dedup();
+        assert_eq!(board.neighbours().iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn shifts_do_not_wrap_around_edges() {
+        for size_check in [
+            check_neighbours::<15> as fn(&[usize]),
+            check_neighbours::<21>,
+        ] {
+            size_check(&[0]);
+            size_check(&[14, 15, 16]);
+            size_check(&[20, 21, 420, 440]);
+        }
+
+        let corner = Bitboard::<21>::square(440);
+        assert!(corner.right().is_empty() && corner.down().is_empty());
+        assert_eq!(corner.up().iter().collect::<Vec<_>>(), [419]);
+        assert_eq!(Bitboard::<21>::FULL.count(), 441);
+        assert_eq!((!Bitboard::<15>::square(3)).count(), 224);
+    }
+
+    proptest! {
+        #[test]
+        fn neighbours_match_row_and_column_arithmetic(
+            squares in prop::collection::vec(0..441usize, 0..40),
+        ) {
+            check_neighbours::<15>(&squares);
+            check_neighbours::<21>(&squares);
+        }
+    }
+}
@@ -1,11 +1,21 @@
 #![allow(dead_code)]
 
-use crate::CrosswordMove;
 use crate::constants::{BOARD_SIZE, BoardPosition, EMPTY_TILE, TOTAL_SIZE};
+use crate::core::{Bitboard, CrosswordMove};
+use crate::error::Error;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Board {
     tiles: [char; TOTAL_SIZE],
     tile_count: usize,
+    /// The squares with a tile, kept in step with `tiles`.
+    occupied: Bitboard,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Board {
@@ -13,6 +23,7 @@ impl Board {
         Self {
             tiles: [EMPTY_TILE; TOTAL_SIZE],
             tile_count: 0,
+            occupied: Bitboard::EMPTY,
         }
     }
 
@@ -27,7 +38,25 @@ impl Board {
     }
 
     pub fn place(&mut self, tile: char, index: BoardPosition) {
-        self.tiles[index as usize] = tile;
+        let index = index as usize;
+        self.tiles[index] = tile;
+        if tile == EMPTY_TILE {
+            self.occupied.clear(index);
+        } else {
+            self.occupied.set(index);
+        }
+    }
+
+    /// Checked `place`: `tile` must be a letter (lowercase for a blank) or `EMPTY_TILE`.
+    pub fn try_place(&mut self, tile: char, index: usize) -> Result<(), Error> {
+        if index >= TOTAL_SIZE {
+            return Err(Error::OutOfBounds(index));
+        }
+        if tile != EMPTY_TILE && !tile.is_ascii_alphabetic() {
+            return Err(Error::InvalidTile(tile));
+        }
+        self.place(tile, index as BoardPosition);
+        Ok(())
     }
 
     pub fn get(&self, index: usize) -> char {
@@ -48,38 +77,253 @@ impl Board {
         self.tile_count -= crossword_move.len();
     }
 
-    pub fn is_anchor(&self, index: usize) -> bool {
-        if !self.is_cell_empty(index as usize) {
-            return false;
+    /// Checked `make_move`: every tile must be a letter going on a distinct empty square.
+    /// The board is left unchanged on error.
+    pub fn try_make_move(&mut self, crossword_move: &CrosswordMove) -> Result<(), Error> {
+        let mut seen = [false; TOTAL_SIZE];
+        for (tile, pos) in crossword_move.iter() {
+            let index = pos as usize;
+            if index >= TOTAL_SIZE {
+                return Err(Error::OutOfBounds(index));
+            }
+            if !tile.is_ascii_alphabetic() {
+                return Err(Error::InvalidTile(tile));
+            }
+            if seen[index] {
+                return Err(Error::DuplicateSquare(index));
+            }
+            if !self.is_cell_empty(index) {
+                return Err(Error::SquareOccupied(index));
+            }
+            seen[index] = true;
         }
 
-        let row = index / BOARD_SIZE;
-        let col = index % BOARD_SIZE;
+        self.make_move(crossword_move);
+        Ok(())
+    }
 
-        // Check left
-        if col > 0 && !self.is_cell_empty(index - 1) {
-            return true;
+    /// Whether `crossword_move` is placed as a play can be: its tiles go on distinct empty
+    /// squares of one row or column with no empty square between them, and touch a tile
+    /// already on the board or, as the first play, cover the centre square with at least
+    /// two tiles. The words it forms are not checked.
+    pub fn try_check_placement(&self, crossword_move: &CrosswordMove) -> Result<(), Error> {
+        self.check_line(crossword_move)?;
+        if self.is_empty() {
+            let covers_centre = crossword_move
+                .iter()
+                .any(|(_, pos)| pos as usize == TOTAL_SIZE / 2);
+            if !covers_centre || crossword_move.len() < 2 {
+                return Err(Error::InvalidMove(
+                    "the first play must cover the centre with at least two tiles",
+                ));
+            }
+        } else if !crossword_move
+            .iter()
+            .any(|(_, pos)| self.has_neighbour(pos as usize))
+        {
+            return Err(Error::InvalidMove("the play does not touch the board"));
         }
+        Ok(())
+    }
 
-        // Check right
-        if col + 1 < BOARD_SIZE && !self.is_cell_empty(index + 1) {
-            return true;
+    /// The tiles of `crossword_move` go on distinct empty squares of one row or column,
+    /// with no empty square between them.
+    pub(crate) fn check_line(&self, crossword_move: &CrosswordMove) -> Result<(), Error> {
+        let mut positions: Vec<usize> =
+            crossword_move.iter().map(|(_, pos)| pos as usize).collect();
+        positions.sort_unstable();
+        if let Some(&pos) = positions.iter().find(|&&pos| pos >= TOTAL_SIZE) {
+            return Err(Error::OutOfBounds(pos));
         }
+        if let Some(pair) = positions.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(Error::DuplicateSquare(pair[0]));
+        }
+        if let Some(&pos) = positions.iter().find(|&&pos| !self.is_cell_empty(pos)) {
+            return Err(Error::SquareOccupied(pos));
+        }
+        let (Some(&first), Some(&last)) = (positions.first(), positions.last()) else {
+            return Ok(());
+        };
+
+        let step = if first / BOARD_SIZE == last / BOARD_SIZE {
+            1
+        } else {
+            BOARD_SIZE
+        };
+        let on_line = positions.iter().all(|&pos| {
+            if step == 1 {
+                pos / BOARD_SIZE == first / BOARD_SIZE
+            } else {
+                pos % BOARD_SIZE == first % BOARD_SIZE
+            }
+        });
+        if !on_line {
+            return Err(Error::InvalidMove("tiles are not on one line"));
+        }
+        let gap = (first..=last)
+            .step_by(step)
+            .any(|pos| self.is_cell_empty(pos) && positions.binary_search(&pos).is_err());
+        if gap {
+            return Err(Error::InvalidMove("an empty square lies between the tiles"));
+        }
+        Ok(())
+    }
 
-        // Check up
-        if row > 0 && !self.is_cell_empty(index - BOARD_SIZE) {
-            return true;
+    /// Checked `undo_move`: every tile of the move must be on the board. The board is left
+    /// unchanged on error.
+    pub fn try_undo_move(&mut self, crossword_move: &CrosswordMove) -> Result<(), Error> {
+        for (tile, pos) in crossword_move.iter() {
+            let index = pos as usize;
+            if index >= TOTAL_SIZE {
+                return Err(Error::OutOfBounds(index));
+            }
+            if self.tiles[index] != tile {
+                return Err(Error::TileMissing(index));
+            }
         }
 
-        // Check down
-        if row + 1 < BOARD_SIZE && !self.is_cell_empty(index + BOARD_SIZE) {
-            return true;
+        // Only reachable when tiles were put down with `place`, which does not count them
+        if self.tile_count < crossword_move.len() {
+            let (_, pos) = crossword_move.iter().next().unwrap_or_default();
+            return Err(Error::TileMissing(pos as usize));
         }
 
-        false
+        self.undo_move(crossword_move);
+        Ok(())
+    }
+
+    /// The squares with a tile.
+    pub fn occupied(&self) -> Bitboard {
+        self.occupied
+    }
+
+    /// Empty squares next to a tile, where every move but the first one has to touch the
+    /// board. Empty on an empty board.
+    pub fn anchors(&self) -> Bitboard {
+        self.occupied.neighbours() & !self.occupied
+    }
+
+    pub fn is_anchor(&self, index: usize) -> bool {
+        !self.occupied.get(index) && self.has_neighbour(index)
+    }
+
+    /// Whether a square left, right, up or down of `index` holds a tile.
+    pub fn has_neighbour(&self, index: usize) -> bool {
+        let row = index / BOARD_SIZE;
+        let col = index % BOARD_SIZE;
+        (col > 0 && self.occupied.get(index - 1))
+            || (col + 1 < BOARD_SIZE && self.occupied.get(index + 1))
+            || (row > 0 && self.occupied.get(index - BOARD_SIZE))
+            || (row + 1 < BOARD_SIZE && self.occupied.get(index + BOARD_SIZE))
     }
 
     pub fn increase_tile_count(&mut self) {
         self.tile_count += 1;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::RACK_SIZE;
+
+    fn crossword_move(tiles: &[(char, BoardPosition)]) -> CrosswordMove {
+        let mut letters = [EMPTY_TILE; RACK_SIZE];
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        for (i, &(tile, pos)) in tiles.iter().enumerate() {
+            letters[i] = tile;
+            positions[i] = pos;
+        }
+        CrosswordMove::from_arrays(letters, positions, tiles.len() as u8)
+    }
+
+    #[test]
+    fn try_place_checks_bounds_and_tiles() {
+        let mut board = Board::new();
+        assert!(board.try_place('q', 0).is_ok());
+        assert_eq!(board.get(0), 'q');
+        assert!(matches!(
+            board.try_place('A', TOTAL_SIZE),
+            Err(Error::OutOfBounds(TOTAL_SIZE))
+        ));
+        assert!(matches!(
+            board.try_place('?', 1),
+            Err(Error::InvalidTile('?'))
+        ));
+        assert!(board.try_place(EMPTY_TILE, 0).is_ok());
+        assert!(board.is_cell_empty(0));
+    }
+
+    #[test]
+    fn try_make_move_rejects_bad_moves_without_changes() {
+        let mut board = Board::new();
+        let cat = crossword_move(&[('C', 111), ('A', 112), ('T', 113)]);
+        board.try_make_move(&cat).unwrap();
+
+        let overlapping = crossword_move(&[('S', 114), ('O', 113)]);
+        assert!(matches!(
+            board.try_make_move(&overlapping),
+            Err(Error::SquareOccupied(113))
+        ));
+        assert!(board.is_cell_empty(114));
+
+        let twice = crossword_move(&[('A', 0), ('B', 0)]);
+        assert!(matches!(
+            board.try_make_move(&twice),
+            Err(Error::DuplicateSquare(0))
+        ));
+        let outside = crossword_move(&[('A', 230)]);
+        assert!(matches!(
+            board.try_make_move(&outside),
+            Err(Error::OutOfBounds(230))
+        ));
+        let digit = crossword_move(&[('1', 0)]);
+        assert!(matches!(
+            board.try_make_move(&digit),
+            Err(Error::InvalidTile('1'))
+        ));
+    }
+
+    #[test]
+    fn try_undo_move_requires_the_tiles_on_the_board() {
+        let mut board = Board::new();
+        let cat = crossword_move(&[('C', 111), ('A', 112), ('T', 113)]);
+        assert!(matches!(
+            board.try_undo_move(&cat),
+            Err(Error::TileMissing(111))
+        ));
+
+        board.make_move(&cat);
+        board.try_undo_move(&cat).unwrap();
+        assert!(board.is_empty());
+        assert!(board.try_undo_move(&cat).is_err());
+
+        // Tiles put down without counting them cannot underflow the tile count
+        board.place('C', 111);
+        assert!(matches!(
+            board.try_undo_move(&crossword_move(&[('C', 111)])),
+            Err(Error::TileMissing(111))
+        ));
+    }
+
+    #[test]
+    fn occupancy_follows_moves() {
+        let mut board = Board::new();
+        assert!(board.anchors().is_empty());
+
+        let cat = crossword_move(&[('C', 111), ('A', 112), ('T', 113)]);
+        board.make_move(&cat);
+        assert_eq!(board.occupied().iter().collect::<Vec<_>>(), [111, 112, 113]);
+        // Both ends of the row and the three squares above and below
+        let anchors: Vec<usize> = board.anchors().iter().collect();
+        assert_eq!(anchors, [96, 97, 98, 110, 114, 126, 127, 128]);
+        assert!((0..TOTAL_SIZE).all(|index| board.is_anchor(index) == anchors.contains(&index)));
+        assert!(board.has_neighbour(111) && !board.is_anchor(111));
+
+        board.undo_move(&cat);
+        assert!(board.occupied().is_empty());
+        board.try_place('q', 0).unwrap();
+        assert_eq!(board.anchors().iter().collect::<Vec<_>>(), [1, 15]);
+        assert!(board.is_anchor(15) && !board.is_anchor(14));
+    }
+}
@@ -1,5 +1,18 @@
 use crate::constants::{BoardPosition, RACK_SIZE};
 
+/// Direction of a move's main word, see `Board::orientation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+impl Orientation {
+    pub fn is_horizontal(self) -> bool {
+        self == Orientation::Horizontal
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct CrosswordMove {
     tiles: [char; RACK_SIZE],
@@ -25,6 +38,11 @@ impl CrosswordMove {
         self.len as usize
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn iter(&self) -> MoveIterator<'_> {
         MoveIterator {
             move_ref: self,
@@ -1,7 +1,17 @@
+mod bitboard;
 mod board;
 mod crossword_move;
+mod notation;
+mod packed_move;
 mod rack;
+mod scoring;
+mod text_format;
 
+pub use bitboard::{Bitboard, MAX_BOARD_SIZE};
 pub use board::Board;
-pub use crossword_move::CrosswordMove;
-pub use rack::Rack;
+pub use crossword_move::{CrosswordMove, Orientation};
+pub(crate) use notation::square_name;
+pub use packed_move::PackedMove;
+pub use rack::{Rack, RackKey};
+pub use scoring::FormedWord;
+pub use text_format::{ParseError, ParseErrorKind, Position};
@@ -0,0 +1,266 @@
+use crate::constants::{BOARD_SIZE, BoardPosition, EMPTY_TILE, RACK_SIZE};
+use crate::core::scoring::placed_tile;
+use crate::core::{Board, CrosswordMove};
+use crate::error::Error;
+
+impl CrosswordMove {
+    /// Standard notation for the move on `board` (before it is played), e.g. `8D HE(LL)O`.
+    ///
+    /// Horizontal plays are written row first (`8D`), vertical plays column first (`D8`).
+    /// Tiles already on the board are wrapped in parentheses and blanks are lowercase.
+    pub fn to_notation(&self, board: &Board) -> String {
+        let is_horizontal = board.is_horizontal_move(self);
+        let (start, word) = self.marked_word(board, is_horizontal);
+        format!("{} {word}", square_name(start, is_horizontal))
+    }
+
+    /// First square of the main word and the word itself, with tiles already on the board
+    /// in parentheses: `HE(LL)O`.
+    pub(crate) fn marked_word(&self, board: &Board, is_horizontal: bool) -> (usize, String) {
+        let first = self.iter().next().map_or(0, |(_, pos)| pos as usize);
+        let span = board.word_span(self, first, is_horizontal);
+
+        let mut word = String::new();
+        let mut playing_through = false;
+        for &pos in &span {
+            match placed_tile(self, pos) {
+                Some(tile) => {
+                    if playing_through {
+                        word.push(')');
+                        playing_through = false;
+                    }
+                    word.push(tile);
+                }
+                None => {
+                    if !playing_through {
+                        word.push('(');
+                        playing_through = true;
+                    }
+                    word.push(board.get(pos));
+                }
+            }
+        }
+        if playing_through {
+            word.push(')');
+        }
+
+        (span[0], word)
+    }
+
+    /// Parse standard notation for a play on `board`, the inverse of `to_notation`.
+    ///
+    /// Tiles already on the board are written in parentheses, `8D HE(LL)O`, or as dots,
+    /// `8D HE..O`; they must match the board. The notation has to cover the whole word, so
+    /// the squares just before and after it must be empty.
+    pub fn from_notation(notation: &str, board: &Board) -> Result<Self, Error> {
+        let invalid = |reason| Error::InvalidNotation {
+            notation: notation.to_string(),
+            reason,
+        };
+
+        let mut parts = notation.split_whitespace();
+        let (Some(coordinate), Some(word), None) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid("expected a coordinate and a word"));
+        };
+        let (start, is_horizontal) =
+            parse_square(coordinate).ok_or_else(|| invalid("invalid coordinate"))?;
+
+        let (line, first) = if is_horizontal {
+            (start / BOARD_SIZE, start % BOARD_SIZE)
+        } else {
+            (start % BOARD_SIZE, start / BOARD_SIZE)
+        };
+        let square = |i: usize| {
+            if is_horizontal {
+                line * BOARD_SIZE + i
+            } else {
+                i * BOARD_SIZE + line
+            }
+        };
+
+        let mut tiles = [EMPTY_TILE; RACK_SIZE];
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        let mut len = 0;
+        let mut playing_through = false;
+        let mut i = first;
+        for c in word.chars() {
+            match c {
+                '(' if !playing_through => playing_through = true,
+                ')' if playing_through => playing_through = false,
+                '(' | ')' => return Err(invalid("unbalanced parentheses")),
+                _ if i >= BOARD_SIZE => return Err(invalid("word runs off the board")),
+                '.' if !playing_through => {
+                    if board.is_cell_empty(square(i)) {
+                        return Err(invalid("no tile to play through"));
+                    }
+                    i += 1;
+                }
+                _ if playing_through => {
+                    if board.is_cell_empty(square(i)) || board.get(square(i)) != c {
+                        return Err(invalid("played-through tiles do not match the board"));
+                    }
+                    i += 1;
+                }
+                _ => {
+                    if !c.is_ascii_alphabetic() {
+                        return Err(invalid("tiles must be letters"));
+                    }
+                    if !board.is_cell_empty(square(i)) {
+                        return Err(invalid("square is already occupied"));
+                    }
+                    if len == RACK_SIZE {
+                        return Err(invalid("too many tiles"));
+                    }
+                    tiles[len] = c;
+                    positions[len] = square(i) as BoardPosition;
+                    len += 1;
+                    i += 1;
+                }
+            }
+        }
+
+        if playing_through {
+            return Err(invalid("unbalanced parentheses"));
+        }
+        if len == 0 {
+            return Err(invalid("no tiles are placed"));
+        }
+        let occupied = |i: usize| !board.is_cell_empty(square(i));
+        if (first > 0 && occupied(first - 1)) || (i < BOARD_SIZE && occupied(i)) {
+            return Err(invalid("word does not include all adjacent tiles"));
+        }
+
+        Ok(CrosswordMove::from_arrays(tiles, positions, len as u8))
+    }
+}
+
+/// Square and direction of a coordinate written by `square_name`.
+fn parse_square(coordinate: &str) -> Option<(usize, bool)> {
+    let is_horizontal = coordinate.starts_with(|c: char| c.is_ascii_digit());
+    let (row, col) = if is_horizontal {
+        coordinate.split_at(coordinate.find(|c: char| !c.is_ascii_digit())?)
+    } else {
+        let (col, row) = coordinate.split_at(coordinate.find(|c: char| c.is_ascii_digit())?);
+        (row, col)
+    };
+
+    let row: usize = row.parse().ok()?;
+    let [col] = col.as_bytes() else {
+        return None;
+    };
+    let col = col.to_ascii_uppercase().checked_sub(b'A')? as usize;
+    if !(1..=BOARD_SIZE).contains(&row) || col >= BOARD_SIZE {
+        return None;
+    }
+    Some(((row - 1) * BOARD_SIZE + col, is_horizontal))
+}
+
+/// Coordinate of a square: row first for horizontal plays (`8H`), column first for
+/// vertical plays (`H8`). Rows are numbered from 1 and columns lettered from `A`.
+pub(crate) fn square_name(pos: usize, is_horizontal: bool) -> String {
+    let row = pos / BOARD_SIZE + 1;
+    let col = (b'A' + (pos % BOARD_SIZE) as u8) as char;
+    if is_horizontal {
+        format!("{row}{col}")
+    } else {
+        format!("{col}{row}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::{BoardPosition, EMPTY_TILE, RACK_SIZE, TOTAL_SIZE};
+    use crate::core::{Board, CrosswordMove};
+    use crate::error::Error;
+
+    fn make_move(tiles: &str, start: usize, step: usize) -> CrosswordMove {
+        let mut tile_arr = [EMPTY_TILE; RACK_SIZE];
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        for (i, tile) in tiles.chars().enumerate() {
+            tile_arr[i] = tile;
+            positions[i] = (start + i * step) as BoardPosition;
+        }
+        CrosswordMove::from_arrays(tile_arr, positions, tiles.len() as u8)
+    }
+
+    #[test]
+    fn opening_move_notation() {
+        let board = Board::new();
+        let cat = make_move("CaT", TOTAL_SIZE / 2 - 1, 1);
+        assert_eq!(cat.to_notation(&board), "8G CaT");
+
+        let down = make_move("CAT", TOTAL_SIZE / 2 - 15, 15);
+        assert_eq!(down.to_notation(&board), "H7 CAT");
+    }
+
+    #[test]
+    fn played_through_tiles_are_parenthesised() {
+        let mut board = Board::new();
+        board.make_move(&make_move("LL", TOTAL_SIZE / 2, 1));
+
+        let hello = CrosswordMove::from_arrays(
+            [
+                'H', 'E', 'O', EMPTY_TILE, EMPTY_TILE, EMPTY_TILE, EMPTY_TILE,
+            ],
+            [110, 111, 114, 0, 0, 0, 0],
+            3,
+        );
+        assert_eq!(hello.to_notation(&board), "8F HE(LL)O");
+
+        let hook = make_move("S", TOTAL_SIZE / 2 + 2, 1);
+        assert_eq!(hook.to_notation(&board), "8H (LL)S");
+    }
+
+    #[test]
+    fn notation_round_trips() {
+        let mut board = Board::new();
+        board.make_move(&make_move("LL", TOTAL_SIZE / 2, 1));
+
+        let hello = CrosswordMove::from_notation("8F HE(LL)O", &board).unwrap();
+        assert_eq!(hello.to_notation(&board), "8F HE(LL)O");
+        assert_eq!(
+            CrosswordMove::from_notation("8F HE..O", &board).unwrap(),
+            hello
+        );
+
+        let down = CrosswordMove::from_notation("I7 a(L)e", &board).unwrap();
+        assert_eq!(down, make_move("ae", TOTAL_SIZE / 2 + 1 - 15, 30));
+        assert_eq!(down.to_notation(&board), "I7 a(L)e");
+    }
+
+    #[test]
+    fn invalid_notation_is_rejected() {
+        let mut board = Board::new();
+        board.make_move(&make_move("LL", TOTAL_SIZE / 2, 1));
+
+        for notation in [
+            "",
+            "8F",
+            "8F HELLO extra",
+            "8 HELLO",
+            "16A AT",
+            "0A AT",
+            "8P AT",
+            "P8 AT",
+            "8F HE(LL",
+            "8F HE)LL(O",
+            "8F HE(LX)O",
+            "8F HELLO",
+            "8I (L)S",
+            "8F HE(L)",
+            "8A ABCDEFGH",
+            "8N ABC",
+            "8F H3",
+            "8H (LL)",
+        ] {
+            assert!(
+                matches!(
+                    CrosswordMove::from_notation(notation, &board),
+                    Err(Error::InvalidNotation { .. })
+                ),
+                "{notation:?} should not parse"
+            );
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use crate::constants::{BOARD_SIZE, BoardPosition, EMPTY_TILE, RACK_SIZE, TOTAL_SIZE};
+use crate::core::{Board, CrosswordMove, Orientation};
+use crate::error::Error;
+
+/// Enough bits for any square: 8 on a 15x15 board, 9 on 21x21.
+const SQUARE_BITS: u32 = usize::BITS - (TOTAL_SIZE - 1).leading_zeros();
+const VERTICAL_BIT: u32 = SQUARE_BITS;
+const LEN_SHIFT: u32 = VERTICAL_BIT + 1;
+const LEN_BITS: u32 = 3;
+const TILES_SHIFT: u32 = LEN_SHIFT + LEN_BITS;
+const TILE_BITS: u32 = 6;
+/// Set on a tile for a blank; the other bits are the letter, 0 for `A`.
+const BLANK_FLAG: u64 = 1 << 5;
+const _: () = assert!(
+    TILES_SHIFT + RACK_SIZE as u32 * TILE_BITS <= u64::BITS && RACK_SIZE < 1 << LEN_BITS,
+    "a move does not fit in 64 bits"
+);
+
+/// A move packed into 64 bits, for endgame search and transposition tables.
+///
+/// From the lowest bit: the square of the first tile placed (8 bits on a 15x15 board, as
+/// many as `TOTAL_SIZE` needs), whether the tiles run down the board (1 bit), the number of
+/// tiles (3 bits), then 6 bits per tile in board order, the letter from `A` = 0 plus 32 for
+/// a blank. The remaining bits are zero.
+///
+/// Squares already occupied are not stored: the tiles go on the next empty squares from
+/// the first one, so packing and unpacking need the board the move is played on. Packing
+/// only takes moves that leave no empty square between their tiles on that board, which
+/// makes it lossless. A single tile is always packed as horizontal. Packing does not
+/// depend on the order of the tiles in the `CrosswordMove`; unpacking gives them in board
+/// order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PackedMove(u64);
+
+impl PackedMove {
+    /// Pack a move to be played on `board`, whose tiles are letters (lowercase for blanks)
+    /// on one line of the board.
+    ///
+    /// # Panics
+    /// If the move cannot be packed; see `try_new`.
+    pub fn new(board: &Board, crossword_move: &CrosswordMove) -> Self {
+        match Self::try_new(board, crossword_move) {
+            Ok(packed) => packed,
+            Err(err) => panic!("cannot pack move: {err}"),
+        }
+    }
+
+    /// Checked `new`: the tiles must be letters, on distinct empty squares of one row or
+    /// column of `board` with no empty square between them.
+    pub fn try_new(board: &Board, crossword_move: &CrosswordMove) -> Result<Self, Error> {
+        board.check_line(crossword_move).map_err(|err| match err {
+            Error::SquareOccupied(_) => Error::InvalidMove("a tile is on an occupied square"),
+            err => err,
+        })?;
+
+        let mut placements: Vec<(BoardPosition, char)> = crossword_move
+            .iter()
+            .map(|(tile, pos)| (pos, tile))
+            .collect();
+        placements.sort_unstable();
+        let Some(&(first, _)) = placements.first() else {
+            return Ok(Self(0));
+        };
+
+        let row = |pos: BoardPosition| pos as usize / BOARD_SIZE;
+        let is_vertical = placements.len() > 1 && row(placements[1].0) != row(first);
+
+        let mut bits = first as u64
+            | (is_vertical as u64) << VERTICAL_BIT
+            | (placements.len() as u64) << LEN_SHIFT;
+        for (i, &(_, tile)) in placements.iter().enumerate() {
+            if !tile.is_ascii_alphabetic() {
+                return Err(Error::InvalidTile(tile));
+            }
+            let mut code = (tile.to_ascii_uppercase() as u8 - b'A') as u64;
+            if tile.is_ascii_lowercase() {
+                code |= BLANK_FLAG;
+            }
+            bits |= code << (TILES_SHIFT + i as u32 * TILE_BITS);
+        }
+        Ok(Self(bits))
+    }
+
+    /// The move on `board`, which must be the board it was packed for.
+    ///
+    /// # Panics
+    /// If the bits do not describe a move on `board`; see `try_unpack`.
+    pub fn unpack(self, board: &Board) -> CrosswordMove {
+        match self.try_unpack(board) {
+            Ok(crossword_move) => crossword_move,
+            Err(err) => panic!("cannot unpack move: {err}"),
+        }
+    }
+
+    /// Checked `unpack`: the bits must come from `PackedMove::new`, or at least describe
+    /// tiles that fit on the empty squares of `board` from the start square on.
+    pub fn try_unpack(self, board: &Board) -> Result<CrosswordMove, Error> {
+        if self.start() as usize >= TOTAL_SIZE {
+            return Err(Error::OutOfBounds(self.start() as usize));
+        }
+        if self.0 >> (TILES_SHIFT + RACK_SIZE as u32 * TILE_BITS) != 0 {
+            return Err(Error::InvalidMove("unused bits are set"));
+        }
+        let len = self.len();
+
+        let mut tiles = [EMPTY_TILE; RACK_SIZE];
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        let (line, mut i) = self.line_start();
+        let is_horizontal = self.orientation().is_horizontal();
+        let square = |i: usize| {
+            if is_horizontal {
+                line * BOARD_SIZE + i
+            } else {
+                i * BOARD_SIZE + line
+            }
+        };
+
+        for (n, (tile, position)) in tiles.iter_mut().zip(&mut positions).take(len).enumerate() {
+            while i < BOARD_SIZE && !board.is_cell_empty(square(i)) {
+                i += 1;
+            }
+            if i >= BOARD_SIZE {
+                return Err(Error::InvalidMove("tiles run off the board"));
+            }
+
+            let code = self.0 >> (TILES_SHIFT + n as u32 * TILE_BITS) & ((1 << TILE_BITS) - 1);
+            let letter = code & !BLANK_FLAG;
+            if letter >= 26 {
+                return Err(Error::InvalidMove("tile code is not a letter"));
+            }
+            let letter = (b'A' + letter as u8) as char;
+            *tile = if code & BLANK_FLAG != 0 {
+                letter.to_ascii_lowercase()
+            } else {
+                letter
+            };
+            *position = square(i) as BoardPosition;
+            i += 1;
+        }
+        Ok(CrosswordMove::from_arrays(tiles, positions, len as u8))
+    }
+
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Square of the first tile placed.
+    pub fn start(self) -> BoardPosition {
+        (self.0 & ((1 << SQUARE_BITS) - 1)) as BoardPosition
+    }
+
+    /// Direction the tiles run in; single tiles are horizontal.
+    pub fn orientation(self) -> Orientation {
+        if self.0 >> VERTICAL_BIT & 1 == 1 {
+            Orientation::Vertical
+        } else {
+            Orientation::Horizontal
+        }
+    }
+
+    /// Number of tiles placed.
+    pub fn len(self) -> usize {
+        (self.0 >> LEN_SHIFT & ((1 << LEN_BITS) - 1)) as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    /// Row or column of the move and the start square's index along it.
+    fn line_start(self) -> (usize, usize) {
+        let start = self.start() as usize;
+        if self.orientation().is_horizontal() {
+            (start / BOARD_SIZE, start % BOARD_SIZE)
+        } else {
+            (start % BOARD_SIZE, start / BOARD_SIZE)
+        }
+    }
+}
+
+impl From<PackedMove> for u64 {
+    fn from(packed: PackedMove) -> Self {
+        packed.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::core::Rack;
+    use crate::move_generation::{Gaddag, MoveGenerator};
+
+    fn placements(crossword_move: &CrosswordMove) -> Vec<(BoardPosition, char)> {
+        let mut placements: Vec<_> = crossword_move.iter().map(|(t, p)| (p, t)).collect();
+        placements.sort_unstable();
+        placements
+    }
+
+    #[test]
+    fn generated_moves_round_trip() {
+        let words = [
+            "CAT", "CATS", "SCAT", "ACT", "ACTS", "AT", "TA", "AS", "TAS", "SAT",
+        ];
+        let gaddag = Gaddag::from_wordlist(&words.map(String::from).to_vec());
+        let mut board = Board::new();
+        board.make_move(&CrosswordMove::from_notation("8G CAT", &board).unwrap());
+
+        let mut rack: Rack = "AST?".parse().unwrap();
+        let moves = MoveGenerator::new(&gaddag).generate_all_moves(&board, &mut rack);
+        assert!(!moves.is_empty());
+        for crossword_move in &moves {
+            let packed = PackedMove::new(&board, crossword_move);
+            assert_eq!(packed.len(), crossword_move.len());
+            let unpacked = packed.unpack(&board);
+            assert_eq!(placements(&unpacked), placements(crossword_move));
+            assert_eq!(PackedMove::new(&board, &unpacked), packed);
+        }
+    }
+
+    #[test]
+    fn invalid_bits_are_rejected() {
+        let board = Board::new();
+        assert!(PackedMove::default().unpack(&board).is_empty());
+        for bits in [
+            // Two tiles from O15 across
+            224 | 2 << LEN_SHIFT,
+            // Tile code 26
+            112 | 1 << LEN_SHIFT | 26 << TILES_SHIFT,
+            // Past the last tile
+            1 << 60,
+        ] {
+            assert!(
+                PackedMove::from_bits(bits).try_unpack(&board).is_err(),
+                "{bits:x}"
+            );
+        }
+
+        let scattered = CrosswordMove::from_arrays(['A'; RACK_SIZE], [0, 16, 0, 0, 0, 0, 0], 2);
+        assert!(PackedMove::try_new(&board, &scattered).is_err());
+
+        // A on H8 and T on J8 would unpack as AT
+        let gapped = CrosswordMove::from_arrays(
+            ['A', 'T', 'A', 'A', 'A', 'A', 'A'],
+            [112, 114, 0, 0, 0, 0, 0],
+            2,
+        );
+        assert!(matches!(
+            PackedMove::try_new(&board, &gapped),
+            Err(Error::InvalidMove(_))
+        ));
+    }
+
+    /// A board with random tiles, and a move of tiles on the next empty squares of a line.
+    /// With `gap`, the empty square after the first tile is skipped.
+    fn board_and_move(gap: bool) -> impl Strategy<Value = (Board, CrosswordMove)> {
+        let tile = prop::sample::select(vec!['A', 'E', 'Q', 'Z', 'a', 'z']);
+        let min_len = if gap { 2 } else { 1 };
+        (
+            prop::collection::vec(any::<bool>(), TOTAL_SIZE),
+            0..TOTAL_SIZE,
+            any::<bool>(),
+            prop::collection::vec(tile, min_len..=RACK_SIZE),
+        )
+            .prop_filter_map(
+                "tiles run off the board",
+                move |(filled, start, down, tiles)| {
+                    let mut board = Board::new();
+                    for (pos, _) in filled.iter().enumerate().filter(|&(_, &f)| f) {
+                        if pos != start {
+                            board.place('E', pos as BoardPosition);
+                            board.increase_tile_count();
+                        }
+                    }
+
+                    let step = if down { BOARD_SIZE } else { 1 };
+                    let wrapped =
+                        |pos: usize| pos >= TOTAL_SIZE || (!down && pos.is_multiple_of(BOARD_SIZE));
+                    // The first empty square from `pos` on, if the line has one
+                    let next_empty = |mut pos: usize| {
+                        while !board.is_cell_empty(pos) {
+                            pos += step;
+                            if wrapped(pos) {
+                                return None;
+                            }
+                        }
+                        Some(pos)
+                    };
+
+                    let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+                    let mut move_tiles = [EMPTY_TILE; RACK_SIZE];
+                    let mut pos = start;
+                    for (i, &tile) in tiles.iter().enumerate() {
+                        if gap && i == 1 {
+                            pos = next_empty(pos)? + step;
+                            if wrapped(pos) {
+                                return None;
+                            }
+                        }
+                        pos = next_empty(pos)?;
+                        positions[i] = pos as BoardPosition;
+                        move_tiles[i] = tile;
+                        pos += step;
+                        if wrapped(pos) && i + 1 < tiles.len() {
+                            return None;
+                        }
+                    }
+                    // Tiles in a scrambled order, as the generator may produce them
+                    positions[..tiles.len()].reverse();
+                    move_tiles[..tiles.len()].reverse();
+                    let len = tiles.len() as u8;
+                    Some((
+                        board,
+                        CrosswordMove::from_arrays(move_tiles, positions, len),
+                    ))
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn packing_round_trips((board, crossword_move) in board_and_move(false)) {
+            let packed = PackedMove::new(&board, &crossword_move);
+            let unpacked = packed.unpack(&board);
+            prop_assert_eq!(placements(&unpacked), placements(&crossword_move));
+            prop_assert_eq!(PackedMove::new(&board, &unpacked), packed);
+            prop_assert_eq!(PackedMove::from_bits(packed.bits()), packed);
+        }
+
+        #[test]
+        fn gapped_moves_are_rejected((board, crossword_move) in board_and_move(true)) {
+            let packed = PackedMove::try_new(&board, &crossword_move);
+            prop_assert!(matches!(packed, Err(Error::InvalidMove(_))), "{:?}", packed);
+        }
+    }
+}
@@ -1,26 +1,103 @@
 #![allow(dead_code)]
 
-use crate::constants::{EMPTY_TILE, RACK_SIZE};
+use std::fmt;
+use std::str::FromStr;
 
+use crate::constants::{BLANK, EMPTY_TILE, INDEX_TO_CHAR, RACK_SIZE, UNIQUE_TILES, try_get_index};
+use crate::error::Error;
+
+/// The tiles of a player. Each of the `RACK_SIZE` slots holds a tile or `EMPTY_TILE`, and
+/// bit `i` of `available_mask` is set while the tile in slot `i` is on the rack; move
+/// generation clears it while the tile is tried on the board.
+///
+/// Besides the slots, a rack is a multiset of tiles: see `add`, `remove`, `counts` and `key`.
+/// `"AEIRST?".parse::<Rack>()` builds a rack from a string, `?` being a blank.
+#[derive(Debug, Clone)]
 pub struct Rack {
     pub tiles: [char; RACK_SIZE],
     pub len: usize,
-    pub used_mask: u8,
+    pub available_mask: u8,
+}
+
+/// The tiles of a rack, leave or exchange as a multiset: how many of each tile there are,
+/// regardless of order. Usable as a map key; displays in canonical order, blanks last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct RackKey([u8; UNIQUE_TILES]);
+
+impl RackKey {
+    /// Count `tiles`; blanks may be given as `?` or as lowercase letters (blanks on the board).
+    /// At most 255 copies of each tile can be counted.
+    pub fn from_tiles(tiles: impl IntoIterator<Item = char>) -> Result<Self, Error> {
+        let mut counts = [0u8; UNIQUE_TILES];
+        for tile in tiles {
+            let tile = if tile.is_ascii_lowercase() {
+                BLANK
+            } else {
+                tile
+            };
+            let count = &mut counts[try_get_index(tile)?];
+            *count = count.checked_add(1).ok_or(Error::TooManyTiles(tile))?;
+        }
+        Ok(Self(counts))
+    }
+
+    /// Number of tiles of each kind, indexed like `INDEX_TO_CHAR`.
+    pub fn counts(&self) -> &[u8; UNIQUE_TILES] {
+        &self.0
+    }
+
+    /// The tiles in canonical order: alphabetical, blanks last.
+    pub fn tiles(&self) -> Vec<char> {
+        INDEX_TO_CHAR
+            .iter()
+            .zip(self.0)
+            .flat_map(|(&tile, count)| std::iter::repeat_n(tile, count as usize))
+            .collect()
+    }
+}
+
+impl fmt::Display for RackKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tiles()
+            .into_iter()
+            .try_for_each(|tile| write!(f, "{tile}"))
+    }
 }
 
 impl Rack {
     pub fn from_arrays(tiles: [char; RACK_SIZE], len: usize) -> Self {
-        let mut used_mask: u8 = 0;
+        let mut available_mask: u8 = 0;
         for (i, tile) in tiles.iter().enumerate() {
             if *tile != EMPTY_TILE {
-                used_mask |= 1 << i;
+                available_mask |= 1 << i;
             }
         }
         Self {
             tiles,
             len,
-            used_mask,
+            available_mask,
+        }
+    }
+
+    /// Checked `from_arrays`: tiles must be letters, `BLANK` or `EMPTY_TILE`, and `len` must
+    /// be the number of non-empty slots.
+    pub fn try_from_arrays(tiles: [char; RACK_SIZE], len: usize) -> Result<Self, Error> {
+        if let Some(&tile) = tiles
+            .iter()
+            .find(|&&t| t != EMPTY_TILE && t != BLANK && !t.is_ascii_uppercase())
+        {
+            return Err(Error::InvalidTile(tile));
         }
+        let rack = Self::from_arrays(tiles, len);
+        if rack.available_mask.count_ones() as usize != len {
+            return Err(Error::InvalidRackLength(len));
+        }
+        Ok(rack)
+    }
+
+    /// A rack without tiles.
+    pub fn empty() -> Self {
+        Self::from_arrays([EMPTY_TILE; RACK_SIZE], 0)
     }
 
     #[inline]
@@ -29,40 +106,157 @@ impl Rack {
     }
 
     #[inline]
-    fn is_index_used(&self, idx: usize) -> bool {
-        (self.used_mask & (1 << idx)) != 0
+    fn is_available(&self, idx: usize) -> bool {
+        (self.available_mask & (1 << idx)) != 0
     }
 
+    /// Take the tile at `idx` off the rack (it is no longer available).
     #[inline]
     pub fn mark_used(&mut self, idx: usize) {
-        self.used_mask |= 1 << idx;
+        self.available_mask &= !(1 << idx);
         self.len -= 1;
     }
 
+    /// Put the tile at `idx` back on the rack.
     #[inline]
     pub fn unmark_used(&mut self, idx: usize) {
-        self.used_mask &= !(1 << idx);
+        self.available_mask |= 1 << idx;
         self.len += 1;
     }
 
+    /// Checked `mark_used`: the slot must hold an available tile.
+    pub fn try_mark_used(&mut self, idx: usize) -> Result<(), Error> {
+        if idx >= RACK_SIZE || self.tiles[idx] == EMPTY_TILE || !self.is_available(idx) {
+            return Err(Error::NoTileAt(idx));
+        }
+        self.mark_used(idx);
+        Ok(())
+    }
+
+    /// Checked `unmark_used`: the slot must hold a tile that was taken off the rack.
+    pub fn try_unmark_used(&mut self, idx: usize) -> Result<(), Error> {
+        if idx >= RACK_SIZE || self.tiles[idx] == EMPTY_TILE {
+            return Err(Error::NoTileAt(idx));
+        }
+        if self.is_available(idx) {
+            return Err(Error::TileAlreadyOnRack(idx));
+        }
+        self.unmark_used(idx);
+        Ok(())
+    }
+
     /// Iterate over available tiles with their indices.
     pub fn available_tiles(&self) -> impl Iterator<Item = (usize, char)> + '_ {
-        // Iterate over tiles that are present on the rack (marked as used)
         self.tiles
             .iter()
             .enumerate()
-            .filter(|(i, _)| self.is_index_used(*i))
+            .filter(|(i, _)| self.is_available(*i))
             .map(|(i, &c)| (i, c))
     }
+
+    /// Put `tile` (a letter or `BLANK`) on the rack, in the first slot without an available
+    /// tile.
+    pub fn add(&mut self, tile: char) -> Result<(), Error> {
+        let tile = tile.to_ascii_uppercase();
+        if tile != BLANK && !tile.is_ascii_uppercase() {
+            return Err(Error::InvalidTile(tile));
+        }
+        let idx = (0..RACK_SIZE)
+            .find(|&i| !self.is_available(i))
+            .ok_or(Error::InvalidRackLength(RACK_SIZE + 1))?;
+        self.tiles[idx] = tile;
+        self.unmark_used(idx);
+        Ok(())
+    }
+
+    /// Take one `tile` off the rack, emptying its slot.
+    pub fn remove(&mut self, tile: char) -> Result<(), Error> {
+        let tile = tile.to_ascii_uppercase();
+        let (idx, _) = self
+            .available_tiles()
+            .find(|&(_, t)| t == tile)
+            .ok_or(Error::TileNotOnRack(tile))?;
+        self.mark_used(idx);
+        self.tiles[idx] = EMPTY_TILE;
+        Ok(())
+    }
+
+    /// Take all of `tiles` off the rack, e.g. for an exchange. The rack is left unchanged
+    /// unless every tile is on it.
+    pub fn remove_all(&mut self, tiles: &str) -> Result<(), Error> {
+        let mut rack = self.clone();
+        for tile in tiles.chars() {
+            rack.remove(tile)?;
+        }
+        *self = rack;
+        Ok(())
+    }
+
+    /// Whether `tile` is on the rack.
+    pub fn contains(&self, tile: char) -> bool {
+        let tile = tile.to_ascii_uppercase();
+        self.available_tiles().any(|(_, t)| t == tile)
+    }
+
+    /// Whether every tile of `tiles` is on the rack, counting repeated tiles.
+    pub fn contains_all(&self, tiles: &str) -> bool {
+        let Ok(needed) = RackKey::from_tiles(tiles.chars().map(|t| t.to_ascii_uppercase())) else {
+            return false;
+        };
+        let counts = self.counts();
+        needed.0.iter().zip(counts).all(|(&n, have)| n <= have)
+    }
+
+    /// Number of each tile on the rack, indexed like `INDEX_TO_CHAR`.
+    pub fn counts(&self) -> [u8; UNIQUE_TILES] {
+        self.key().0
+    }
+
+    /// The tiles on the rack as a multiset, usable as a map key.
+    pub fn key(&self) -> RackKey {
+        let mut counts = [0u8; UNIQUE_TILES];
+        for (_, tile) in self.available_tiles() {
+            if let Ok(idx) = try_get_index(tile) {
+                counts[idx] += 1;
+            }
+        }
+        RackKey(counts)
+    }
+}
+
+impl FromStr for Rack {
+    type Err = Error;
+
+    /// Parse tiles like `AEIRST?`, in any case, with `?` for blanks.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let count = s.chars().count();
+        if count > RACK_SIZE {
+            return Err(Error::InvalidRackLength(count));
+        }
+
+        let mut rack = Rack::empty();
+        for tile in s.chars() {
+            rack.add(tile)?;
+        }
+        Ok(rack)
+    }
+}
+
+/// The tiles in canonical order, e.g. `AEIRST?`.
+impl fmt::Display for Rack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.key().fmt(f)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::constants::{EMPTY_TILE, RACK_SIZE};
+    use crate::constants::{BLANK, EMPTY_TILE, RACK_SIZE};
+    use crate::error::Error;
 
     #[test]
-    fn test_from_arrays_sets_used_mask_and_len() {
+    fn test_from_arrays_sets_available_mask_and_len() {
         // build an array with two tiles placed at indices 0 and 3
         let mut tiles = [EMPTY_TILE; RACK_SIZE];
         tiles[0] = 'A';
@@ -72,11 +266,11 @@ mod tests {
         let rack = Rack::from_arrays(tiles, len);
 
         let expected_mask: u8 = (1 << 0) | (1 << 3);
-        assert_eq!(rack.used_mask, expected_mask);
+        assert_eq!(rack.available_mask, expected_mask);
         assert_eq!(rack.len, len);
-        assert!(rack.is_index_used(0));
-        assert!(rack.is_index_used(3));
-        assert!(!rack.is_index_used(1));
+        assert!(rack.is_available(0));
+        assert!(rack.is_available(3));
+        assert!(!rack.is_available(1));
     }
 
     #[test]
@@ -95,18 +289,124 @@ mod tests {
 
     #[test]
     fn test_mark_and_unmark_used_updates_mask_and_len() {
-        let tiles = [EMPTY_TILE; RACK_SIZE];
+        let tiles = ['A', 'B', 'C', 'D', 'E', 'F', 'G'];
         // start with all slots available and len = RACK_SIZE
         let mut rack = Rack::from_arrays(tiles, RACK_SIZE);
 
-        // mark index 2 as used
+        // mark index 2 as used: the tile is no longer available
         rack.mark_used(2);
-        assert!(rack.is_index_used(2));
+        assert!(!rack.is_available(2));
         assert_eq!(rack.len, RACK_SIZE - 1);
+        assert!(rack.available_tiles().all(|(_, tile)| tile != 'C'));
 
         // unmark it and ensure state is restored
         rack.unmark_used(2);
-        assert!(!rack.is_index_used(2));
+        assert!(rack.is_available(2));
         assert_eq!(rack.len, RACK_SIZE);
     }
+
+    #[test]
+    fn checked_rack_operations_reject_bad_input() {
+        let mut tiles = [EMPTY_TILE; RACK_SIZE];
+        tiles[0] = 'A';
+        tiles[1] = BLANK;
+        assert!(matches!(
+            Rack::try_from_arrays(tiles, 3),
+            Err(Error::InvalidRackLength(3))
+        ));
+        tiles[2] = '1';
+        assert!(matches!(
+            Rack::try_from_arrays(tiles, 3),
+            Err(Error::InvalidTile('1'))
+        ));
+        tiles[2] = EMPTY_TILE;
+
+        let mut rack = Rack::try_from_arrays(tiles, 2).unwrap();
+        rack.try_mark_used(0).unwrap();
+        assert!(matches!(rack.try_mark_used(0), Err(Error::NoTileAt(0))));
+        assert!(matches!(rack.try_mark_used(2), Err(Error::NoTileAt(2))));
+        assert!(matches!(
+            rack.try_mark_used(RACK_SIZE),
+            Err(Error::NoTileAt(RACK_SIZE))
+        ));
+        assert_eq!(rack.len, 1);
+
+        rack.try_unmark_used(0).unwrap();
+        assert!(matches!(
+            rack.try_unmark_used(0),
+            Err(Error::TileAlreadyOnRack(0))
+        ));
+        assert_eq!(rack.len, 2);
+    }
+
+    #[test]
+    fn racks_parse_from_strings() {
+        let rack: Rack = "tsria?e".parse().unwrap();
+        assert_eq!(rack.len, 7);
+        assert_eq!(rack.to_string(), "AEIRST?");
+        assert!(rack.contains('?') && rack.contains('s'));
+        assert!(!rack.contains('Q'));
+
+        assert!(Rack::from_str("").unwrap().is_empty());
+        assert!(matches!(
+            Rack::from_str("ABCDEFGH"),
+            Err(Error::InvalidRackLength(8))
+        ));
+        assert!(matches!(Rack::from_str("A1"), Err(Error::InvalidTile('1'))));
+    }
+
+    #[test]
+    fn racks_are_multisets() {
+        let mut rack: Rack = "EEST".parse().unwrap();
+        assert_eq!(rack.counts()[4], 2);
+        assert!(rack.contains_all("ESE"));
+        assert!(!rack.contains_all("EEE"));
+
+        rack.remove('E').unwrap();
+        assert_eq!(rack.to_string(), "EST");
+        assert!(matches!(rack.remove('Q'), Err(Error::TileNotOnRack('Q'))));
+
+        // Exchanges are all or nothing
+        assert!(matches!(
+            rack.remove_all("SX"),
+            Err(Error::TileNotOnRack('X'))
+        ));
+        assert_eq!(rack.to_string(), "EST");
+        rack.remove_all("ts").unwrap();
+        assert_eq!(rack.to_string(), "E");
+
+        for tile in "?QRSTU".chars() {
+            rack.add(tile).unwrap();
+        }
+        assert_eq!(rack.to_string(), "EQRSTU?");
+        assert!(matches!(rack.add('A'), Err(Error::InvalidRackLength(8))));
+    }
+
+    #[test]
+    fn rack_keys_ignore_order() {
+        let a: Rack = "SATIRE?".parse().unwrap();
+        let b: Rack = "?ERITAS".parse().unwrap();
+        assert_eq!(a.key(), b.key());
+        assert_ne!(a.key(), "SATIRE".parse::<Rack>().unwrap().key());
+
+        let mut leaves = std::collections::HashMap::new();
+        leaves.insert(a.key(), 1);
+        assert_eq!(leaves.get(&b.key()), Some(&1));
+
+        // Blanks played on the board count as blanks
+        let key = RackKey::from_tiles("ERa?".chars()).unwrap();
+        assert_eq!(key.to_string(), "ER??");
+        assert_eq!(key.tiles(), vec!['E', 'R', '?', '?']);
+        assert!(RackKey::from_tiles("E1".chars()).is_err());
+        assert_eq!(
+            RackKey::from_tiles("E".repeat(255).chars())
+                .unwrap()
+                .counts()[4],
+            255
+        );
+        assert!(matches!(
+            RackKey::from_tiles("E".repeat(256).chars()),
+            Err(Error::TooManyTiles('E'))
+        ));
+    }
 }
@@ -0,0 +1,299 @@
+use crate::constants::{
+    BINGO_BONUS, BOARD_SIZE, BoardPosition, DOUBLE_LETTER, DOUBLE_WORD, QUADRUPLE_LETTER,
+    QUADRUPLE_WORD, RACK_SIZE, TILE_BONUSES, TRIPLE_LETTER, TRIPLE_WORD, tile_score,
+};
+use crate::core::{Board, CrosswordMove, Orientation, square_name};
+
+/// A word formed by a move, see `Board::formed_words`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FormedWord {
+    /// The word as it reads on the board, blanks lowercase.
+    pub word: String,
+    /// Square of the first letter.
+    pub start: BoardPosition,
+    pub orientation: Orientation,
+    pub score: u32,
+}
+
+impl FormedWord {
+    /// Coordinate of the first letter in move notation, e.g. `8H` across or `H8` down.
+    pub fn coordinate(&self) -> String {
+        square_name(self.start as usize, self.orientation.is_horizontal())
+    }
+}
+
+impl Board {
+    /// Score a move as if it were played on the current board, i.e. call this before
+    /// `make_move`. Counts the main word, every cross-word and the bingo bonus.
+    pub fn score_move(&self, crossword_move: &CrosswordMove) -> u32 {
+        let is_horizontal = self.is_horizontal_move(crossword_move);
+
+        let mut score = 0;
+        let main_word = self.word_span(
+            crossword_move,
+            first_position(crossword_move),
+            is_horizontal,
+        );
+        if main_word.len() > 1 {
+            score += self.score_span(crossword_move, &main_word);
+        }
+
+        for (_, pos) in crossword_move.iter() {
+            let cross_word = self.word_span(crossword_move, pos as usize, !is_horizontal);
+            if cross_word.len() > 1 {
+                score += self.score_span(crossword_move, &cross_word);
+            }
+        }
+
+        if crossword_move.len() == RACK_SIZE {
+            score += BINGO_BONUS;
+        }
+
+        score
+    }
+
+    /// Direction of the main word formed by a move, the one its notation is written in.
+    ///
+    /// A single tile forms at most one word each way, so it needs a convention: it counts as
+    /// horizontal when it touches a tile to its left or right, and as vertical otherwise.
+    /// A tile that extends words both ways is horizontal, and one that touches nothing, as
+    /// on an empty board, vertical.
+    pub fn orientation(&self, crossword_move: &CrosswordMove) -> Orientation {
+        if self.is_horizontal_move(crossword_move) {
+            Orientation::Horizontal
+        } else {
+            Orientation::Vertical
+        }
+    }
+
+    /// `orientation` as a flag, for the line-based helpers.
+    pub(crate) fn is_horizontal_move(&self, crossword_move: &CrosswordMove) -> bool {
+        let mut positions = crossword_move.iter().map(|(_, pos)| pos as usize);
+        let Some(first) = positions.next() else {
+            return true;
+        };
+
+        if crossword_move.len() > 1 {
+            return positions.all(|pos| pos / BOARD_SIZE == first / BOARD_SIZE);
+        }
+
+        let col = first % BOARD_SIZE;
+        (col > 0 && !self.is_cell_empty(first - 1))
+            || (col + 1 < BOARD_SIZE && !self.is_cell_empty(first + 1))
+    }
+
+    /// All squares of the contiguous word running through `pos`, with the tiles of the move
+    /// counted as already placed.
+    pub(crate) fn word_span(
+        &self,
+        crossword_move: &CrosswordMove,
+        pos: usize,
+        is_horizontal: bool,
+    ) -> Vec<usize> {
+        let (line, mut start) = if is_horizontal {
+            (pos / BOARD_SIZE, pos % BOARD_SIZE)
+        } else {
+            (pos % BOARD_SIZE, pos / BOARD_SIZE)
+        };
+        let to_pos = |i: usize| {
+            if is_horizontal {
+                line * BOARD_SIZE + i
+            } else {
+                i * BOARD_SIZE + line
+            }
+        };
+        let occupied = |i: usize| {
+            let square = to_pos(i);
+            !self.is_cell_empty(square) || placed_tile(crossword_move, square).is_some()
+        };
+
+        while start > 0 && occupied(start - 1) {
+            start -= 1;
+        }
+        let mut end = start;
+        while end + 1 < BOARD_SIZE && occupied(end + 1) {
+            end += 1;
+        }
+
+        (start..=end).map(to_pos).collect()
+    }
+
+    /// The words a move forms, main word first, as they would read on the board (blanks
+    /// lowercase). A tile that forms no word of two or more letters counts as a word itself,
+    /// scoring nothing. The word scores add up to `score_move` without the bingo bonus.
+    pub fn formed_words(&self, crossword_move: &CrosswordMove) -> Vec<FormedWord> {
+        let is_horizontal = self.is_horizontal_move(crossword_move);
+        let formed_word = |span: Vec<usize>, is_horizontal: bool| FormedWord {
+            word: span
+                .iter()
+                .map(|&pos| placed_tile(crossword_move, pos).unwrap_or_else(|| self.get(pos)))
+                .collect(),
+            start: span[0] as BoardPosition,
+            orientation: if is_horizontal {
+                Orientation::Horizontal
+            } else {
+                Orientation::Vertical
+            },
+            score: if span.len() > 1 {
+                self.score_span(crossword_move, &span)
+            } else {
+                0
+            },
+        };
+
+        let main_word = formed_word(
+            self.word_span(
+                crossword_move,
+                first_position(crossword_move),
+                is_horizontal,
+            ),
+            is_horizontal,
+        );
+        let mut words: Vec<FormedWord> = crossword_move
+            .iter()
+            .map(|(_, pos)| {
+                formed_word(
+                    self.word_span(crossword_move, pos as usize, !is_horizontal),
+                    !is_horizontal,
+                )
+            })
+            .filter(|word| word.word.len() > 1)
+            .collect();
+        if main_word.word.len() > 1 || words.is_empty() {
+            words.insert(0, main_word);
+        }
+        words
+    }
+
+    fn score_span(&self, crossword_move: &CrosswordMove, span: &[usize]) -> u32 {
+        let mut word_score = 0;
+        let mut word_multiplier = 1;
+
+        for &pos in span {
+            let Some(tile) = placed_tile(crossword_move, pos) else {
+                // Premium squares only count for newly placed tiles
+                word_score += tile_score(self.get(pos));
+                continue;
+            };
+
+            let letter_score = tile_score(tile);
+            word_score += match TILE_BONUSES[pos] {
+                DOUBLE_LETTER => letter_score * 2,
+                TRIPLE_LETTER => letter_score * 3,
+                QUADRUPLE_LETTER => letter_score * 4,
+                _ => letter_score,
+            };
+            word_multiplier *= match TILE_BONUSES[pos] {
+                DOUBLE_WORD => 2,
+                TRIPLE_WORD => 3,
+                QUADRUPLE_WORD => 4,
+                _ => 1,
+            };
+        }
+
+        word_score * word_multiplier
+    }
+}
+
+fn first_position(crossword_move: &CrosswordMove) -> usize {
+    crossword_move
+        .iter()
+        .next()
+        .map_or(0, |(_, pos)| pos as usize)
+}
+
+/// The tile a move places on `pos`, if any.
+pub(crate) fn placed_tile(crossword_move: &CrosswordMove, pos: usize) -> Option<char> {
+    crossword_move
+        .iter()
+        .find(|&(_, placed)| placed as usize == pos)
+        .map(|(tile, _)| tile)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constants::{BoardPosition, EMPTY_TILE, RACK_SIZE, TOTAL_SIZE};
+    use crate::core::{Board, CrosswordMove};
+
+    fn make_move(tiles: &str, start: usize, step: usize) -> CrosswordMove {
+        let mut tile_arr = [EMPTY_TILE; RACK_SIZE];
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        for (i, tile) in tiles.chars().enumerate() {
+            tile_arr[i] = tile;
+            positions[i] = (start + i * step) as BoardPosition;
+        }
+        CrosswordMove::from_arrays(tile_arr, positions, tiles.len() as u8)
+    }
+
+    #[test]
+    fn opening_move_doubles_on_center() {
+        let board = Board::new();
+        // CAT across through the centre (H8, a double word square)
+        let cat = make_move("CAT", TOTAL_SIZE / 2 - 1, 1);
+        assert_eq!(board.score_move(&cat), (3 + 1 + 1) * 2);
+    }
+
+    #[test]
+    fn blanks_score_zero() {
+        let board = Board::new();
+        let cat = make_move("CaT", TOTAL_SIZE / 2 - 1, 1);
+        assert_eq!(board.score_move(&cat), (3 + 1) * 2);
+    }
+
+    #[test]
+    fn hook_scores_main_word_and_cross_word() {
+        let mut board = Board::new();
+        let cat = make_move("CAT", TOTAL_SIZE / 2 - 1, 1);
+        board.make_move(&cat);
+
+        // S hooks CAT to make CATS; alone it is a single-tile play
+        let s = make_move("S", TOTAL_SIZE / 2 + 2, 1);
+        assert!(board.is_horizontal_move(&s));
+        assert_eq!(board.score_move(&s), 3 + 1 + 1 + 1);
+
+        // AS down from the A: only forms the main word
+        let down = make_move("S", TOTAL_SIZE / 2 + 15, 15);
+        assert!(!board.is_horizontal_move(&down));
+        assert_eq!(board.score_move(&down), 1 + 1);
+    }
+
+    #[test]
+    fn formed_words_include_cross_words() {
+        let mut board = Board::new();
+        board.make_move(&make_move("CAT", TOTAL_SIZE / 2 - 1, 1));
+
+        let words = |board: &Board, m: &CrosswordMove| -> Vec<(String, String, u32)> {
+            board
+                .formed_words(m)
+                .into_iter()
+                .map(|w| (w.coordinate(), w.word, w.score))
+                .collect()
+        };
+        let word = |coordinate: &str, word: &str, score| (coordinate.into(), word.into(), score);
+
+        // At under CA(T): At across, CA and At down. The A doubles on G9, the blank t
+        // scores nothing
+        let under = make_move("At", TOTAL_SIZE / 2 - 1 + 15, 1);
+        assert_eq!(
+            words(&board, &under),
+            [
+                word("9G", "At", 2),
+                word("G8", "CA", 5),
+                word("H8", "At", 1)
+            ]
+        );
+        assert_eq!(board.score_move(&under), 2 + 5 + 1);
+
+        let s = make_move("S", TOTAL_SIZE / 2 + 2, 1);
+        assert_eq!(words(&board, &s), [word("8G", "CATS", 6)]);
+        assert_eq!(words(&Board::new(), &s), [word("J8", "S", 0)]);
+    }
+
+    #[test]
+    fn bingo_bonus_is_added() {
+        let board = Board::new();
+        let bingo = make_move("RETAINS", TOTAL_SIZE / 2 - 3, 1);
+        // Seven one-point letters, doubled on the centre, plus the bingo bonus
+        assert_eq!(board.score_move(&bingo), 7 * 2 + 50);
+    }
+}
@@ -0,0 +1,390 @@
+//! Plain-text format for boards and positions.
+//!
+//! A board is `BOARD_SIZE` lines of `BOARD_SIZE` tiles: `.` for an empty square, uppercase
+//! letters for tiles and lowercase letters for blanks. A position is a board followed by
+//! optional `key: value` metadata lines:
+//!
+//! ```text
+//! rack1: AEIRST?
+//! rack2: DGOU
+//! score1: 120
+//! score2: 87
+//! bag: ABCEEILNOQ
+//! turn: 1
+//! ```
+//!
+//! Blank lines among the metadata are ignored. Players are numbered from 1 in the text and
+//! from 0 in `Position`.
+
+use std::fmt;
+
+use crate::constants::{BLANK, BOARD_SIZE, BoardPosition, EMPTY_TILE, PLAYER_COUNT};
+use crate::core::Board;
+
+/// Where and why a board or position failed to parse. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    InvalidTile(char),
+    RowTooShort(usize),
+    RowTooLong,
+    WrongRowCount(usize),
+    MalformedMetadata,
+    UnknownKey(String),
+    DuplicateKey(String),
+    InvalidValue(String),
+}
+
+/// A board together with what is known about racks, scores, the bag and the player to move.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Position {
+    pub board: Board,
+    pub racks: [Option<String>; PLAYER_COUNT],
+    pub scores: [Option<u32>; PLAYER_COUNT],
+    pub bag: Option<String>,
+    pub to_move: Option<usize>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidTile(tile) => write!(f, "invalid tile '{tile}'"),
+            ParseErrorKind::RowTooShort(len) => {
+                write!(f, "row has {len} squares, expected {BOARD_SIZE}")
+            }
+            ParseErrorKind::RowTooLong => write!(f, "row has more than {BOARD_SIZE} squares"),
+            ParseErrorKind::WrongRowCount(rows) => {
+                write!(f, "board has {rows} rows, expected {BOARD_SIZE}")
+            }
+            ParseErrorKind::MalformedMetadata => write!(f, "expected 'key: value'"),
+            ParseErrorKind::UnknownKey(key) => write!(f, "unknown key '{key}'"),
+            ParseErrorKind::DuplicateKey(key) => write!(f, "'{key}' is given more than once"),
+            ParseErrorKind::InvalidValue(value) => write!(f, "invalid value '{value}'"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+}
+
+impl Board {
+    /// Parse a board written as `BOARD_SIZE` rows of tiles, see the module docs.
+    pub fn from_text(text: &str) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = text.lines().collect();
+        if lines.len() > BOARD_SIZE {
+            return Err(ParseError::new(
+                BOARD_SIZE + 1,
+                1,
+                ParseErrorKind::WrongRowCount(lines.len()),
+            ));
+        }
+        parse_rows(&lines)
+    }
+
+    /// Write the board in the format read by `from_text`, one row per line.
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity((BOARD_SIZE + 1) * BOARD_SIZE);
+        for row in 0..BOARD_SIZE {
+            for col in 0..BOARD_SIZE {
+                text.push(self.get(row * BOARD_SIZE + col));
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl Position {
+    pub fn from_text(text: &str) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = text.lines().collect();
+        let board_lines = &lines[..lines.len().min(BOARD_SIZE)];
+        let mut position = Position {
+            board: parse_rows(board_lines)?,
+            ..Position::default()
+        };
+
+        let mut seen: Vec<&str> = Vec::new();
+        for (i, line) in lines.iter().enumerate().skip(BOARD_SIZE) {
+            let line_no = i + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Some((raw_key, raw_value)) = line.split_once(':') else {
+                return Err(ParseError::new(
+                    line_no,
+                    1,
+                    ParseErrorKind::MalformedMetadata,
+                ));
+            };
+            let key = raw_key.trim();
+            let value = raw_value.trim();
+            let value_column = line.chars().count() - raw_value.trim_start().chars().count() + 1;
+
+            if seen.contains(&key) {
+                return Err(ParseError::new(
+                    line_no,
+                    1,
+                    ParseErrorKind::DuplicateKey(key.to_string()),
+                ));
+            }
+            seen.push(key);
+
+            let invalid = || {
+                ParseError::new(
+                    line_no,
+                    value_column,
+                    ParseErrorKind::InvalidValue(value.to_string()),
+                )
+            };
+
+            match key {
+                "rack1" | "rack2" => {
+                    let player = player_from_key(key);
+                    position.racks[player] = Some(parse_tiles(value, line_no, value_column)?);
+                }
+                "score1" | "score2" => {
+                    let player = player_from_key(key);
+                    position.scores[player] = Some(value.parse().map_err(|_| invalid())?);
+                }
+                "bag" => position.bag = Some(parse_tiles(value, line_no, value_column)?),
+                "turn" => {
+                    let player: usize = value.parse().map_err(|_| invalid())?;
+                    if !(1..=PLAYER_COUNT).contains(&player) {
+                        return Err(invalid());
+                    }
+                    position.to_move = Some(player - 1);
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        line_no,
+                        1,
+                        ParseErrorKind::UnknownKey(key.to_string()),
+                    ));
+                }
+            }
+        }
+
+        Ok(position)
+    }
+
+    /// Write the position in the format read by `from_text`. Metadata that is not known is
+    /// left out, so parsing the result gives back an equal position.
+    pub fn to_text(&self) -> String {
+        let mut text = self.board.to_text();
+        let mut push = |key: &str, value: &str| {
+            text.push_str(key);
+            text.push(':');
+            if !value.is_empty() {
+                text.push(' ');
+                text.push_str(value);
+            }
+            text.push('\n');
+        };
+
+        for (player, rack) in self.racks.iter().enumerate() {
+            if let Some(rack) = rack {
+                push(&format!("rack{}", player + 1), rack);
+            }
+        }
+        for (player, score) in self.scores.iter().enumerate() {
+            if let Some(score) = score {
+                push(&format!("score{}", player + 1), &score.to_string());
+            }
+        }
+        if let Some(bag) = &self.bag {
+            push("bag", bag);
+        }
+        if let Some(player) = self.to_move {
+            push("turn", &(player + 1).to_string());
+        }
+
+        text
+    }
+}
+
+fn parse_rows(lines: &[&str]) -> Result<Board, ParseError> {
+    let mut board = Board::new();
+
+    for (row, line) in lines.iter().enumerate() {
+        let mut len = 0;
+        for (col, tile) in line.chars().enumerate() {
+            if col >= BOARD_SIZE {
+                return Err(ParseError::new(
+                    row + 1,
+                    col + 1,
+                    ParseErrorKind::RowTooLong,
+                ));
+            }
+            len += 1;
+            if tile == EMPTY_TILE {
+                continue;
+            }
+            if !tile.is_ascii_alphabetic() {
+                return Err(ParseError::new(
+                    row + 1,
+                    col + 1,
+                    ParseErrorKind::InvalidTile(tile),
+                ));
+            }
+            board.place(tile, (row * BOARD_SIZE + col) as BoardPosition);
+            board.increase_tile_count();
+        }
+
+        if len < BOARD_SIZE {
+            return Err(ParseError::new(
+                row + 1,
+                len + 1,
+                ParseErrorKind::RowTooShort(len),
+            ));
+        }
+    }
+
+    if lines.len() < BOARD_SIZE {
+        return Err(ParseError::new(
+            lines.len() + 1,
+            1,
+            ParseErrorKind::WrongRowCount(lines.len()),
+        ));
+    }
+
+    Ok(board)
+}
+
+/// Rack and bag contents: uppercase letters and `?` for blanks.
+fn parse_tiles(value: &str, line: usize, column: usize) -> Result<String, ParseError> {
+    for (i, tile) in value.chars().enumerate() {
+        if !tile.is_ascii_uppercase() && tile != BLANK {
+            return Err(ParseError::new(
+                line,
+                column + i,
+                ParseErrorKind::InvalidTile(tile),
+            ));
+        }
+    }
+    Ok(value.to_string())
+}
+
+fn player_from_key(key: &str) -> usize {
+    if key.ends_with('1') { 0 } else { 1 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::TOTAL_SIZE;
+
+    fn board_text(middle_row: &str) -> String {
+        let empty = ".".repeat(BOARD_SIZE);
+        let mut rows = vec![empty.as_str(); BOARD_SIZE];
+        rows[BOARD_SIZE / 2] = middle_row;
+        rows.iter().map(|row| format!("{row}\n")).collect()
+    }
+
+    #[test]
+    fn board_round_trips() {
+        let text = board_text(".....HElLO.....");
+        let board = Board::from_text(&text).unwrap();
+
+        assert_eq!(board.get(TOTAL_SIZE / 2 - 2), 'H');
+        assert_eq!(board.get(TOTAL_SIZE / 2), 'l');
+        assert!(!board.is_empty());
+        assert_eq!(board.to_text(), text);
+    }
+
+    #[test]
+    fn position_round_trips() {
+        let mut text = board_text("......CAT......");
+        text.push_str("rack1: AEIRST?\nrack2: DGOU\nscore1: 12\nscore2: 0\nbag:\nturn: 2\n");
+
+        let position = Position::from_text(&text).unwrap();
+        assert_eq!(position.racks[0].as_deref(), Some("AEIRST?"));
+        assert_eq!(position.scores, [Some(12), Some(0)]);
+        assert_eq!(position.bag.as_deref(), Some(""));
+        assert_eq!(position.to_move, Some(1));
+        assert_eq!(position.to_text(), text);
+
+        let board_only = Position::from_text(&board_text("......CAT......")).unwrap();
+        assert_eq!(board_only.racks, [None, None]);
+        assert_eq!(
+            Position::from_text(&board_only.to_text()).unwrap(),
+            board_only
+        );
+    }
+
+    #[test]
+    fn errors_point_at_line_and_column() {
+        let err = Board::from_text(&board_text("......C*T......")).unwrap_err();
+        assert_eq!((err.line, err.column), (8, 8));
+        assert_eq!(err.kind, ParseErrorKind::InvalidTile('*'));
+        assert_eq!(err.to_string(), "line 8, column 8: invalid tile '*'");
+
+        let err = Board::from_text(&board_text("......CAT")).unwrap_err();
+        assert_eq!((err.line, err.column), (8, 10));
+        assert_eq!(err.kind, ParseErrorKind::RowTooShort(9));
+
+        let err = Board::from_text(&board_text("......CAT.......")).unwrap_err();
+        assert_eq!((err.line, err.column), (8, 16));
+
+        let err = Board::from_text("...............\n").unwrap_err();
+        assert_eq!((err.line, err.kind), (2, ParseErrorKind::WrongRowCount(1)));
+    }
+
+    #[test]
+    fn metadata_errors() {
+        let base = board_text("......CAT......");
+
+        let err = Position::from_text(&format!("{base}rack1: AB1\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (16, 10));
+
+        let err = Position::from_text(&format!("{base}score2: lots\n")).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidValue("lots".into()));
+
+        let err = Position::from_text(&format!("{base}turn: 3\n")).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::InvalidValue("3".into()));
+
+        let err = Position::from_text(&format!("{base}bag: A\nbag: B\n")).unwrap_err();
+        assert_eq!(
+            (err.line, err.kind),
+            (17, ParseErrorKind::DuplicateKey("bag".into()))
+        );
+
+        let err = Position::from_text(&format!("{base}colour: red\n")).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::UnknownKey("colour".into()));
+
+        let err = Position::from_text(&format!("{base}nonsense\n")).unwrap_err();
+        assert_eq!(err.kind, ParseErrorKind::MalformedMetadata);
+
+        // Columns count characters, not bytes
+        let err = Position::from_text(&format!("{base}rack1:\u{a0}AB1\n")).unwrap_err();
+        assert_eq!((err.line, err.column), (16, 10));
+    }
+
+    #[test]
+    fn blank_lines_around_metadata_are_ignored() {
+        let base = board_text("......CAT......");
+        let position = Position::from_text(&format!("{base}\nrack1: AB\n  \nturn: 2\n\n")).unwrap();
+        assert_eq!(position.racks[0].as_deref(), Some("AB"));
+        assert_eq!(position.to_move, Some(1));
+    }
+}
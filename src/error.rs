@@ -0,0 +1,106 @@
+use std::{fmt, io};
+
+use crate::constants::{RACK_SIZE, TOTAL_SIZE};
+use crate::core::ParseError;
+
+/// Errors returned by the fallible (`try_`) entry points of the crate.
+///
+/// The infallible versions of the same functions stay available for the hot paths of move
+/// generation, where the input has already been validated; they panic on the cases below.
+#[derive(Debug)]
+pub enum Error {
+    /// A character that is not a tile, e.g. a digit in a word or rack.
+    InvalidTile(char),
+    /// A board index of `TOTAL_SIZE` or more.
+    OutOfBounds(usize),
+    /// A move puts a tile on a square that already has one.
+    SquareOccupied(usize),
+    /// A move puts two tiles on the same square.
+    DuplicateSquare(usize),
+    /// A move being undone has a tile that is not on the board.
+    TileMissing(usize),
+    /// A rack index of `RACK_SIZE` or more, or one without a tile on the rack.
+    NoTileAt(usize),
+    /// A tile is put back on a rack slot that already holds it.
+    TileAlreadyOnRack(usize),
+    /// A tile is taken off a rack that does not have it.
+    TileNotOnRack(char),
+    /// A rack whose length does not match its tiles, or with more than `RACK_SIZE` tiles.
+    InvalidRackLength(usize),
+    /// More copies of a tile than a `RackKey` can count.
+    TooManyTiles(char),
+    /// A word list entry that cannot be stored, with its 1-based line number.
+    InvalidWord {
+        line: usize,
+        word: String,
+    },
+    /// Move notation that does not describe a play on the board, with the reason.
+    InvalidNotation {
+        notation: String,
+        reason: &'static str,
+    },
+    /// A move not placed as a play can be, or a packed move that cannot be unpacked, with
+    /// the reason; see `Board::try_check_placement` and `PackedMove`.
+    InvalidMove(&'static str),
+    /// A game action the rules do not allow at this point, with the reason.
+    IllegalAction(&'static str),
+    Parse(ParseError),
+    Io(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidTile(tile) => write!(f, "invalid tile '{tile}'"),
+            Error::OutOfBounds(index) => write!(
+                f,
+                "board index {index} is out of bounds (board has {TOTAL_SIZE} squares)"
+            ),
+            Error::SquareOccupied(index) => write!(f, "square {index} is already occupied"),
+            Error::DuplicateSquare(index) => {
+                write!(f, "move places more than one tile on square {index}")
+            }
+            Error::TileMissing(index) => write!(f, "square {index} does not hold the move's tile"),
+            Error::NoTileAt(index) => write!(f, "no tile on rack slot {index}"),
+            Error::TileAlreadyOnRack(index) => {
+                write!(f, "rack slot {index} already holds its tile")
+            }
+            Error::TileNotOnRack(tile) => write!(f, "tile '{tile}' is not on the rack"),
+            Error::InvalidRackLength(len) => write!(
+                f,
+                "invalid rack length {len} (racks hold at most {RACK_SIZE} tiles)"
+            ),
+            Error::TooManyTiles(tile) => write!(f, "more than {} copies of tile '{tile}'", u8::MAX),
+            Error::InvalidWord { line, word } => write!(f, "line {line}: invalid word '{word}'"),
+            Error::InvalidNotation { notation, reason } => {
+                write!(f, "invalid move notation '{notation}': {reason}")
+            }
+            Error::InvalidMove(reason) => write!(f, "invalid move: {reason}"),
+            Error::IllegalAction(reason) => write!(f, "illegal action: {reason}"),
+            Error::Parse(err) => err.fmt(f),
+            Error::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(err) => Some(err),
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt;
+
+use crate::constants::{BOARD_SIZE, BoardPosition};
+use crate::core::{Board, CrosswordMove, Orientation, Rack, square_name};
+use crate::evaluation::{EvaluatedMove, leave_after};
+
+/// Where and how a move lies on the board, on top of the tiles it places: the direction
+/// and extent of its main word, what it scores and what it leaves on the rack.
+///
+/// `CrosswordMove` only holds the placed tiles, which is all move generation needs; these
+/// are worked out from the board the move is played on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MoveDetails {
+    pub crossword_move: CrosswordMove,
+    /// See `Board::orientation` for single tiles.
+    pub orientation: Orientation,
+    /// Square of the first letter of the main word, which may already be on the board.
+    pub start: BoardPosition,
+    /// The whole main word, tiles already on the board in parentheses: `HE(LL)O`.
+    pub word: String,
+    pub score: u32,
+    /// Rack tiles kept after the move, sorted alphabetically with blanks last.
+    pub leave: Vec<char>,
+}
+
+impl MoveDetails {
+    /// Details of `crossword_move` played from `rack` on `board` (before it is played).
+    pub fn new(board: &Board, rack: &Rack, crossword_move: CrosswordMove) -> Self {
+        let orientation = board.orientation(&crossword_move);
+        let (start, word) = crossword_move.marked_word(board, orientation.is_horizontal());
+        Self {
+            orientation,
+            start: start as BoardPosition,
+            word,
+            score: board.score_move(&crossword_move),
+            leave: leave_after(rack, &crossword_move),
+            crossword_move,
+        }
+    }
+
+    /// Number of squares the main word covers, played-through tiles included.
+    pub fn word_len(&self) -> usize {
+        self.word.chars().filter(|&c| c != '(' && c != ')').count()
+    }
+
+    /// Square of the last letter of the main word.
+    pub fn end(&self) -> BoardPosition {
+        let step = match self.orientation {
+            Orientation::Horizontal => 1,
+            Orientation::Vertical => BOARD_SIZE,
+        };
+        (self.start as usize + (self.word_len() - 1) * step) as BoardPosition
+    }
+}
+
+/// Standard notation, the same as `CrosswordMove::to_notation`.
+impl fmt::Display for MoveDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let square = square_name(self.start as usize, self.orientation.is_horizontal());
+        write!(f, "{square} {}", self.word)
+    }
+}
+
+impl EvaluatedMove {
+    /// Details of the move on the board it was evaluated on.
+    pub fn details(&self, board: &Board) -> MoveDetails {
+        let orientation = board.orientation(&self.crossword_move);
+        let (start, word) = self
+            .crossword_move
+            .marked_word(board, orientation.is_horizontal());
+        MoveDetails {
+            crossword_move: self.crossword_move.clone(),
+            orientation,
+            start: start as BoardPosition,
+            word,
+            score: self.score,
+            leave: self.leave.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::TOTAL_SIZE;
+    use crate::evaluation::evaluate_moves;
+
+    fn details(board: &Board, rack: &str, notation: &str) -> MoveDetails {
+        let crossword_move = CrosswordMove::from_notation(notation, board).unwrap();
+        MoveDetails::new(board, &rack.parse().unwrap(), crossword_move)
+    }
+
+    #[test]
+    fn main_word_extent_includes_played_through_tiles() {
+        let mut board = Board::new();
+        board.make_move(&CrosswordMove::from_notation("8G CAT", &board).unwrap());
+
+        let scat = details(&board, "SEW", "H7 S(A)W");
+        assert_eq!(scat.orientation, Orientation::Vertical);
+        assert_eq!(scat.start as usize, TOTAL_SIZE / 2 - 15);
+        assert_eq!(scat.end() as usize, TOTAL_SIZE / 2 + 15);
+        assert_eq!((scat.word.as_str(), scat.word_len()), ("S(A)W", 3));
+        assert_eq!(scat.leave, ['E']);
+        assert_eq!(scat.to_string(), "H7 S(A)W");
+
+        let cats = details(&board, "S", "8G (CAT)S");
+        assert_eq!(cats.orientation, Orientation::Horizontal);
+        assert_eq!(cats.start as usize, TOTAL_SIZE / 2 - 1);
+        assert_eq!(cats.score, 6);
+        assert!(cats.leave.is_empty());
+    }
+
+    #[test]
+    fn single_tiles_have_a_canonical_orientation() {
+        // Touching nothing, a tile is vertical; extending a word across, horizontal
+        let alone = details(&Board::new(), "A", "H8 A");
+        assert_eq!(alone.orientation, Orientation::Vertical);
+
+        // Hooking CAT across and IT down at once, it is horizontal
+        let mut board = Board::new();
+        board.make_move(&CrosswordMove::from_notation("8G CAT", &board).unwrap());
+        board.make_move(&CrosswordMove::from_notation("J6 IT", &board).unwrap());
+        let both = details(&board, "S", "8G (CAT)S");
+        assert_eq!(both.orientation, Orientation::Horizontal);
+        assert_eq!(
+            board.formed_words(&both.crossword_move)[1].coordinate(),
+            "J6"
+        );
+    }
+
+    #[test]
+    fn evaluated_moves_give_the_same_details() {
+        let board = Board::new();
+        let rack: Rack = "CATS".parse().unwrap();
+        let cat = CrosswordMove::from_notation("8G CAT", &board).unwrap();
+        let evaluated = &evaluate_moves(&board, &rack, [cat.clone()])[0];
+        assert_eq!(
+            evaluated.details(&board),
+            MoveDetails::new(&board, &rack, cat)
+        );
+    }
+}
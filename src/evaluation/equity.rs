@@ -0,0 +1,34 @@
+use crate::core::{Board, CrosswordMove, Rack};
+use crate::evaluation::{leave_after, leave_value};
+
+/// A generated move together with what it scores and what it leaves on the rack.
+#[derive(Clone, Debug)]
+pub struct EvaluatedMove {
+    pub crossword_move: CrosswordMove,
+    pub score: u32,
+    pub leave: Vec<char>,
+    /// Score plus the estimated value of the leave.
+    pub equity: f32,
+}
+
+/// Score and value every move for `rack` on `board` (before any of them is played).
+pub fn evaluate_moves(
+    board: &Board,
+    rack: &Rack,
+    moves: impl IntoIterator<Item = CrosswordMove>,
+) -> Vec<EvaluatedMove> {
+    moves
+        .into_iter()
+        .map(|crossword_move| {
+            let score = board.score_move(&crossword_move);
+            let leave = leave_after(rack, &crossword_move);
+            let equity = score as f32 + leave_value(&leave);
+            EvaluatedMove {
+                crossword_move,
+                score,
+                leave,
+                equity,
+            }
+        })
+        .collect()
+}
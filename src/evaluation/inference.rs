@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use crate::constants::{BLANK, RACK_SIZE};
+use crate::core::{Board, CrosswordMove, Rack, RackKey};
+use crate::evaluation::{evaluate_moves, leave_after, leave_value};
+use crate::game::Rng;
+use crate::move_generation::{Gaddag, MoveGenerator};
+
+/// How `infer_leaves` samples and weights leaves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InferenceConfig {
+    /// Number of leaves drawn from the unseen tiles.
+    pub samples: usize,
+    /// Equity, in points, that makes a leave `e` times less likely when the rack would have
+    /// had a play that much better than the one made.
+    pub temperature: f32,
+}
+
+impl Default for InferenceConfig {
+    fn default() -> Self {
+        Self {
+            samples: 200,
+            temperature: 5.0,
+        }
+    }
+}
+
+/// The leaves an opponent may have kept after their last play, with their probabilities.
+///
+/// An empty inference (the default) knows nothing, and `draw_rack` draws from the unseen
+/// tiles at random.
+#[derive(Clone, Debug, Default)]
+pub struct RackInference {
+    leaves: Vec<(RackKey, f64)>,
+}
+
+/// Weight the leaves the opponent may have kept after playing `played` on `board` (the
+/// board before the play). `unseen` are the tiles the opponent might hold now.
+///
+/// Leaves are drawn from `unseen`, so each is sampled about as often as the opponent would
+/// have drawn it. A leave's weight falls off with how much more equity the rack it
+/// completes could have had from its best move than from `played`: racks on which the
+/// play was the best choice explain it best.
+pub fn infer_leaves(
+    gaddag: &Gaddag,
+    board: &Board,
+    played: &CrosswordMove,
+    unseen: &[char],
+    config: &InferenceConfig,
+    rng: &mut Rng,
+) -> RackInference {
+    let played_tiles: Vec<char> = played
+        .iter()
+        .map(|(tile, _)| {
+            if tile.is_ascii_lowercase() {
+                BLANK
+            } else {
+                tile
+            }
+        })
+        .collect();
+    let leave_len = RACK_SIZE
+        .saturating_sub(played_tiles.len())
+        .min(unseen.len());
+    let generator = MoveGenerator::new(gaddag);
+
+    let mut weights: HashMap<RackKey, f64> = HashMap::new();
+    let mut equity_loss: HashMap<RackKey, f32> = HashMap::new();
+    for _ in 0..config.samples {
+        let leave = draw(unseen.to_vec(), leave_len, rng);
+        let key = RackKey::from_tiles(leave.iter().copied()).expect("unseen tiles are tiles");
+
+        // Each distinct rack only needs its moves generated once
+        let loss = *equity_loss.entry(key).or_insert_with(|| {
+            let rack: Rack = played_tiles
+                .iter()
+                .chain(&leave)
+                .collect::<String>()
+                .parse()
+                .expect("a play and its leave fill at most one rack");
+            let moves = generator.generate_all_moves(board, &mut rack.clone());
+            let best = evaluate_moves(board, &rack, moves)
+                .iter()
+                .map(|m| m.equity)
+                .fold(f32::MIN, f32::max);
+            let made = board.score_move(played) as f32 + leave_value(&leave_after(&rack, played));
+            (best - made).max(0.0)
+        });
+        *weights.entry(key).or_default() +=
+            (-(loss / config.temperature.max(f32::EPSILON)) as f64).exp();
+    }
+
+    let total: f64 = weights.values().sum();
+    let mut leaves: Vec<(RackKey, f64)> = weights
+        .into_iter()
+        .filter(|&(_, weight)| weight > 0.0)
+        .map(|(key, weight)| (key, weight / total))
+        .collect();
+    leaves.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    RackInference { leaves }
+}
+
+impl RackInference {
+    /// The possible leaves, most likely first; the probabilities add up to 1.
+    pub fn leaves(&self) -> &[(RackKey, f64)] {
+        &self.leaves
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Probability that the leave holds at least one `tile`.
+    pub fn tile_probability(&self, tile: char) -> f64 {
+        self.leaves
+            .iter()
+            .filter(|(key, _)| key.tiles().contains(&tile))
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    /// A rack for the opponent: a leave drawn by probability, filled up with tiles drawn
+    /// at random from the rest of `unseen`.
+    pub fn draw_rack(&self, unseen: &[char], rng: &mut Rng) -> Rack {
+        let mut pool = unseen.to_vec();
+        let mut tiles = Vec::new();
+
+        if !self.leaves.is_empty() {
+            let mut target = rng.next_f64();
+            let (leave, _) = self
+                .leaves
+                .iter()
+                .find(|(_, p)| {
+                    target -= p;
+                    target < 0.0
+                })
+                .unwrap_or(&self.leaves[self.leaves.len() - 1]);
+            for tile in leave.tiles() {
+                // Leaves come from an earlier `unseen`; skip tiles that have left it since
+                if let Some(i) = pool.iter().position(|&t| t == tile) {
+                    tiles.push(pool.swap_remove(i));
+                }
+            }
+        }
+
+        let missing = RACK_SIZE.saturating_sub(tiles.len());
+        tiles.extend(draw(pool, missing, rng));
+        tiles
+            .into_iter()
+            .collect::<String>()
+            .parse()
+            .expect("at most RACK_SIZE tiles are drawn")
+    }
+}
+
+/// Up to `count` tiles drawn from `pool` without replacement.
+fn draw(mut pool: Vec<char>, count: usize, rng: &mut Rng) -> Vec<char> {
+    (0..count.min(pool.len()))
+        .map(|_| {
+            let i = rng.below(pool.len());
+            pool.swap_remove(i)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_that_would_have_played_better_are_unlikely() {
+        let words = ["CAT", "CATZ", "ZA"].map(String::from).to_vec();
+        let gaddag = Gaddag::from_wordlist(&words);
+        let board = Board::new();
+        let cat = CrosswordMove::from_notation("8G CAT", &board).unwrap();
+
+        // Four of these eight are kept: half of all leaves hold the Z, but with it CATZ
+        // would have scored three times as much
+        let unseen: Vec<char> = "VVVVVVVZ".chars().collect();
+        let inference = infer_leaves(
+            &gaddag,
+            &board,
+            &cat,
+            &unseen,
+            &InferenceConfig::default(),
+            &mut Rng::new(3),
+        );
+
+        let total: f64 = inference.leaves().iter().map(|(_, p)| p).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert_eq!(inference.leaves()[0].0.to_string(), "VVVV");
+        assert!(inference.tile_probability('Z') < 0.1);
+        assert_eq!(inference.tile_probability('V'), 1.0);
+    }
+
+    #[test]
+    fn drawn_racks_start_from_a_leave() {
+        let words = ["CAT"].map(String::from).to_vec();
+        let gaddag = Gaddag::from_wordlist(&words);
+        let board = Board::new();
+        let cat = CrosswordMove::from_notation("8G CAT", &board).unwrap();
+        let unseen: Vec<char> = "EEEEQ".chars().collect();
+        let config = InferenceConfig::default();
+        let inference = infer_leaves(&gaddag, &board, &cat, &unseen, &config, &mut Rng::new(1));
+
+        let mut rng = Rng::new(2);
+        let unseen_now: Vec<char> = "EEEEQSSSSSS".chars().collect();
+        for _ in 0..10 {
+            let rack = inference.draw_rack(&unseen_now, &mut rng);
+            assert_eq!(rack.len, RACK_SIZE);
+            let leave = rack.to_string();
+            assert!(leave.contains("EEEE") || leave.contains("EEEQ"), "{leave}");
+        }
+
+        let uniform = RackInference::default().draw_rack(&unseen_now, &mut rng);
+        assert_eq!(uniform.len, RACK_SIZE);
+    }
+}
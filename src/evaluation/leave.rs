@@ -0,0 +1,94 @@
+use crate::constants::{BLANK, UNIQUE_TILES, get_index};
+use crate::core::{CrosswordMove, Rack};
+
+/// Rough value, in points, of keeping each tile on the rack after a play.
+/// Indexed in the same order as `INDEX_TO_CHAR`.
+const TILE_LEAVE_VALUES: [f32; UNIQUE_TILES] = [
+    1.0, -2.0, 0.5, 0.5, 2.5, -2.0, -2.0, 0.5, -0.5, -2.5, -1.5, -0.5, 0.5, 0.5, -1.5, -0.5, -7.0,
+    1.5, 8.0, 0.0, -3.0, -5.0, -3.5, 3.5, -0.5, 3.0, 25.0,
+];
+
+/// Penalty for every extra copy of a letter kept on the rack.
+const DUPLICATE_PENALTY: f32 = 3.0;
+
+/// Penalty per tile that the vowel/consonant split is off by more than one.
+const VOWEL_IMBALANCE_PENALTY: f32 = 1.5;
+
+/// Tiles left on the rack after playing `crossword_move`, sorted alphabetically with blanks
+/// last. Lowercase tiles in the move are blanks and use up a `?` from the rack.
+pub fn leave_after(rack: &Rack, crossword_move: &CrosswordMove) -> Vec<char> {
+    let mut leave: Vec<char> = rack.available_tiles().map(|(_, tile)| tile).collect();
+    for (tile, _) in crossword_move.iter() {
+        let rack_tile = if tile.is_ascii_lowercase() {
+            BLANK
+        } else {
+            tile
+        };
+        if let Some(i) = leave.iter().position(|&t| t == rack_tile) {
+            leave.swap_remove(i);
+        }
+    }
+    leave.sort_by_key(|&tile| get_index(tile));
+    leave
+}
+
+/// Static estimate of how much a leave is worth towards future turns.
+pub fn leave_value(leave: &[char]) -> f32 {
+    let mut counts = [0u8; UNIQUE_TILES];
+    let mut value = 0.0;
+    let mut vowels: i32 = 0;
+    let mut consonants: i32 = 0;
+
+    for &tile in leave {
+        let idx = get_index(tile);
+        value += TILE_LEAVE_VALUES[idx];
+        if counts[idx] > 0 && tile != BLANK {
+            value -= DUPLICATE_PENALTY;
+        }
+        counts[idx] += 1;
+
+        match tile {
+            'A' | 'E' | 'I' | 'O' | 'U' => vowels += 1,
+            BLANK => {}
+            _ => consonants += 1,
+        }
+    }
+
+    let imbalance = (vowels - consonants).abs();
+    if imbalance > 1 {
+        value -= VOWEL_IMBALANCE_PENALTY * (imbalance - 1) as f32;
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{BoardPosition, EMPTY_TILE, RACK_SIZE};
+
+    #[test]
+    fn leave_removes_played_tiles_and_blanks() {
+        let rack = Rack::from_arrays(['C', 'A', 'T', 'S', '?', 'E', EMPTY_TILE], 6);
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        positions[..3].copy_from_slice(&[110, 111, 112]);
+        let cat = CrosswordMove::from_arrays(
+            [
+                'C', 'a', 'T', EMPTY_TILE, EMPTY_TILE, EMPTY_TILE, EMPTY_TILE,
+            ],
+            positions,
+            3,
+        );
+
+        assert_eq!(leave_after(&rack, &cat), vec!['A', 'E', 'S']);
+    }
+
+    #[test]
+    fn good_leaves_beat_bad_leaves() {
+        assert!(leave_value(&['E', 'R', 'S']) > leave_value(&['Q', 'U', 'V']));
+        assert!(leave_value(&['?']) > leave_value(&['S']));
+        assert!(leave_value(&['E', 'S']) > leave_value(&['I', 'I']));
+        assert!(leave_value(&['A', 'E', 'I', 'O']) < leave_value(&['A', 'E', 'R', 'T']));
+        assert_eq!(leave_value(&[]), 0.0);
+    }
+}
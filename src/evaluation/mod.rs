@@ -0,0 +1,11 @@
+mod details;
+mod equity;
+mod inference;
+mod leave;
+mod simulation;
+
+pub use details::MoveDetails;
+pub use equity::{EvaluatedMove, evaluate_moves};
+pub use inference::{InferenceConfig, RackInference, infer_leaves};
+pub use leave::{leave_after, leave_value};
+pub use simulation::{SimulatedMove, SimulationConfig, simulate};
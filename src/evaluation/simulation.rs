@@ -0,0 +1,135 @@
+use crate::core::{Board, Rack};
+use crate::evaluation::{EvaluatedMove, InferenceConfig, RackInference, leave_value};
+use crate::game::Rng;
+use crate::move_generation::{Gaddag, MoveGenerator};
+
+/// How much work `simulate` does per turn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimulationConfig {
+    /// Number of moves, best equity first, that are simulated.
+    pub candidates: usize,
+    /// Number of opponent racks each candidate is played against.
+    pub iterations: usize,
+    /// Infer the opponent's leave from their last play, see `infer_leaves`, instead of
+    /// drawing their racks from the unseen tiles at random.
+    pub inference: Option<InferenceConfig>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            candidates: 8,
+            iterations: 32,
+            inference: None,
+        }
+    }
+}
+
+/// A candidate move with its simulated value: score, minus the best reply on average, plus
+/// the value of the leave.
+#[derive(Clone, Debug)]
+pub struct SimulatedMove {
+    pub evaluated: EvaluatedMove,
+    pub value: f32,
+}
+
+/// Look one reply ahead for the best moves by equity.
+///
+/// Each iteration draws an opponent rack from `unseen`, or from `opponent` when their leave
+/// was inferred, and plays every candidate against the opponent's highest-scoring reply on
+/// that rack; all candidates see the same racks. Returns the candidates sorted by value,
+/// best first.
+pub fn simulate(
+    gaddag: &Gaddag,
+    board: &Board,
+    mut moves: Vec<EvaluatedMove>,
+    unseen: &[char],
+    opponent: Option<&RackInference>,
+    config: &SimulationConfig,
+    rng: &mut Rng,
+) -> Vec<SimulatedMove> {
+    moves.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+    moves.truncate(config.candidates);
+
+    let opponent = opponent.cloned().unwrap_or_default();
+    let racks: Vec<Rack> = (0..config.iterations)
+        .map(|_| opponent.draw_rack(unseen, rng))
+        .collect();
+    let generator = MoveGenerator::new(gaddag);
+
+    let mut simulated: Vec<SimulatedMove> = moves
+        .into_iter()
+        .map(|evaluated| {
+            let mut after = board.clone();
+            after.make_move(&evaluated.crossword_move);
+
+            let replies: u32 = racks
+                .iter()
+                .map(|rack| best_score(&generator, &after, &mut rack.clone()))
+                .sum();
+            let mean_reply = if racks.is_empty() {
+                0.0
+            } else {
+                replies as f32 / racks.len() as f32
+            };
+
+            let value = evaluated.score as f32 - mean_reply + leave_value(&evaluated.leave);
+            SimulatedMove { evaluated, value }
+        })
+        .collect();
+
+    simulated.sort_by(|a, b| b.value.total_cmp(&a.value));
+    simulated
+}
+
+fn best_score(generator: &MoveGenerator, board: &Board, rack: &mut Rack) -> u32 {
+    generator
+        .generate_all_moves(board, rack)
+        .iter()
+        .map(|crossword_move| board.score_move(crossword_move))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluation::evaluate_moves;
+
+    #[test]
+    fn known_opponent_rack_gives_exact_values() {
+        let words = ["AX", "AXE", "EX", "XI", "AE", "AI", "TAE", "TEA", "ETA"];
+        let gaddag = Gaddag::from_wordlist(&words.map(String::from).to_vec());
+        let board = Board::new();
+        let rack: Rack = "AET".parse().unwrap();
+        let generator = MoveGenerator::new(&gaddag);
+
+        let moves = generator.generate_all_moves(&board, &mut rack.clone());
+        let evaluated = evaluate_moves(&board, &rack, moves);
+        let config = SimulationConfig {
+            candidates: 4,
+            iterations: 3,
+            inference: None,
+        };
+        // With no more unseen tiles than a rack holds, every iteration draws the same rack
+        let simulated = simulate(
+            &gaddag,
+            &board,
+            evaluated,
+            &['I', 'X'],
+            None,
+            &config,
+            &mut Rng::new(1),
+        );
+
+        assert_eq!(simulated.len(), 4);
+        assert!(simulated.windows(2).all(|w| w[0].value >= w[1].value));
+        for SimulatedMove { evaluated, value } in &simulated {
+            let mut after = board.clone();
+            after.make_move(&evaluated.crossword_move);
+            let reply = best_score(&generator, &after, &mut "IX".parse().unwrap());
+            let expected = evaluated.score as f32 - reply as f32 + leave_value(&evaluated.leave);
+            assert!((value - expected).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,443 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::constants::{BLANK, EMPTY_TILE, PLAYER_COUNT, RACK_SIZE};
+use crate::core::{Board, CrosswordMove, Rack};
+use crate::evaluation::{
+    EvaluatedMove, SimulationConfig, evaluate_moves, leave_after, leave_value, simulate,
+};
+use crate::game::gcg::gcg_play;
+use crate::game::state::rack_tile;
+use crate::game::{Action, Bag, Game, Rng};
+use crate::move_generation::{Gaddag, MoveGenerator};
+use crate::render::escape_json;
+
+/// How `analyse` judges the turns of a game.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnalysisConfig {
+    /// Equity lost, in points, from which a turn is a blunder.
+    pub blunder_threshold: f32,
+    /// Value moves by simulating replies instead of by equity, see `simulate`.
+    pub simulation: Option<SimulationConfig>,
+    /// Seed of the simulations.
+    pub seed: u64,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self {
+            blunder_threshold: 10.0,
+            simulation: None,
+            seed: 0,
+        }
+    }
+}
+
+/// A turn of the game next to the best play the engine finds for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnAnalysis {
+    /// Index of the turn in `Game::turns`.
+    pub turn: usize,
+    pub player: usize,
+    pub rack: String,
+    /// What was played, as in GCG: notation, `-TILES` for an exchange, `-` for a pass.
+    pub played: String,
+    /// The play was a phony and was withdrawn; it is valued as a pass.
+    pub withdrawn: bool,
+    pub played_value: f32,
+    /// The best play found, or `None` when there was no play or nothing better than what
+    /// was done.
+    pub best: Option<String>,
+    pub best_value: f32,
+    /// How much worse the turn was than the best play, never negative.
+    pub equity_lost: f32,
+    pub is_blunder: bool,
+}
+
+/// Every turn of a game compared with the engine's choice, see `analyse`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    pub turns: Vec<TurnAnalysis>,
+    pub blunder_threshold: f32,
+    /// Values come from simulation rather than static equity.
+    pub simulated: bool,
+}
+
+/// Re-analyse every turn of `game`: the best play by equity, or by simulated value with
+/// `config.simulation`, against what was actually done.
+///
+/// Exchanges and passes are valued by the tiles they keep, and simulated like a play that
+/// places nothing. Only plays are searched for the best move, so an exchange that beats
+/// every play counts as the best choice.
+pub fn analyse(gaddag: &Gaddag, game: &Game, config: &AnalysisConfig) -> Analysis {
+    let generator = MoveGenerator::new(gaddag);
+    let all_tiles = Bag::new(0).tiles().to_vec();
+    let mut board = Board::new();
+    let mut turns = Vec::new();
+
+    for (index, turn) in game.turns().iter().enumerate() {
+        let rack: Rack = turn.rack.parse().expect("turns record valid racks");
+        let withdrawn = turn.is_withdrawn();
+        let (played, actual) = match &turn.action {
+            Action::Play(crossword_move) if !withdrawn => (
+                gcg_notation(turn.notation.as_deref()),
+                evaluate_moves(&board, &rack, [crossword_move.clone()]).remove(0),
+            ),
+            Action::Play(_) => (gcg_notation(turn.notation.as_deref()), keeping(&rack, &[])),
+            Action::Exchange(tiles) => (
+                format!("-{}", tiles.iter().collect::<String>()),
+                keeping(&rack, tiles),
+            ),
+            Action::Pass => ("-".to_string(), keeping(&rack, &[])),
+        };
+
+        let moves = generator.generate_all_moves(&board, &mut rack.clone());
+        let evaluated = evaluate_moves(&board, &rack, moves);
+        let (best, best_value, played_value) = match &config.simulation {
+            Some(simulation) => {
+                let unseen = unseen(&all_tiles, &board, &rack);
+                // Both calls draw the same opponent racks from the same seed
+                let rng = Rng::new(Rng::derive_seed(config.seed, index as u64));
+                let best = simulate(
+                    gaddag,
+                    &board,
+                    evaluated,
+                    &unseen,
+                    None,
+                    simulation,
+                    &mut rng.clone(),
+                )
+                .into_iter()
+                .next()
+                .map(|m| (m.evaluated, m.value));
+                let played = simulate(
+                    gaddag,
+                    &board,
+                    vec![actual],
+                    &unseen,
+                    None,
+                    &SimulationConfig {
+                        candidates: 1,
+                        ..*simulation
+                    },
+                    &mut rng.clone(),
+                );
+                let (best, best_value) = best.unzip();
+                (best, best_value, played[0].value)
+            }
+            None => {
+                let best = evaluated
+                    .into_iter()
+                    .max_by(|a, b| a.equity.total_cmp(&b.equity));
+                let best_value = best.as_ref().map(|m| m.equity);
+                (best, best_value, actual.equity)
+            }
+        };
+
+        let equity_lost = best_value.map_or(0.0, |best| (best - played_value).max(0.0));
+        let best = best
+            .filter(|_| equity_lost > 0.0)
+            .map(|m| gcg_notation(Some(&m.crossword_move.to_notation(&board))));
+        turns.push(TurnAnalysis {
+            turn: index,
+            player: turn.player,
+            rack: turn.rack.clone(),
+            played,
+            withdrawn,
+            played_value,
+            best,
+            best_value: best_value.unwrap_or(played_value).max(played_value),
+            equity_lost,
+            is_blunder: equity_lost >= config.blunder_threshold,
+        });
+
+        if let Action::Play(crossword_move) = &turn.action
+            && !withdrawn
+        {
+            board.make_move(crossword_move);
+        }
+    }
+
+    Analysis {
+        turns,
+        blunder_threshold: config.blunder_threshold,
+        simulated: config.simulation.is_some(),
+    }
+}
+
+impl Analysis {
+    /// How close `player` came to the best plays, from 0 to 100: every turn counts 100 for
+    /// the best play down to 0 at the blunder threshold. 100 if they had no turns.
+    pub fn accuracy(&self, player: usize) -> f64 {
+        let scores: Vec<f64> = self
+            .turns
+            .iter()
+            .filter(|turn| turn.player == player)
+            .map(|turn| {
+                let threshold = self.blunder_threshold.max(f32::EPSILON);
+                (1.0 - (turn.equity_lost / threshold) as f64).max(0.0)
+            })
+            .collect();
+        if scores.is_empty() {
+            return 100.0;
+        }
+        100.0 * scores.iter().sum::<f64>() / scores.len() as f64
+    }
+
+    pub fn blunders(&self) -> impl Iterator<Item = &TurnAnalysis> {
+        self.turns.iter().filter(|turn| turn.is_blunder)
+    }
+
+    /// A table of the turns followed by every player's accuracy.
+    pub fn write_text(&self, names: [&str; PLAYER_COUNT], out: &mut impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "{:>4}  {:<12} {:<7}  {:<18} {:>7}  {:<18} {:>7} {:>6}",
+            "Turn", "Player", "Rack", "Played", "Value", "Best", "Value", "Lost"
+        )?;
+        for turn in &self.turns {
+            let played = if turn.withdrawn {
+                format!("{} *", turn.played)
+            } else {
+                turn.played.clone()
+            };
+            let blunder = if turn.is_blunder { "  blunder" } else { "" };
+            writeln!(
+                out,
+                "{:>4}  {:<12} {:<7}  {:<18} {:>7.1}  {:<18} {:>7.1} {:>6.1}{blunder}",
+                turn.turn + 1,
+                names[turn.player],
+                turn.rack,
+                played,
+                turn.played_value,
+                turn.best.as_deref().unwrap_or("="),
+                turn.best_value,
+                turn.equity_lost
+            )?;
+        }
+
+        let value = if self.simulated {
+            "simulation"
+        } else {
+            "equity"
+        };
+        writeln!(
+            out,
+            "\nBy {value}; * withdrawn phony, = nothing better found, blunders lose {:.1} or more",
+            self.blunder_threshold
+        )?;
+        for (player, name) in names.iter().enumerate() {
+            writeln!(
+                out,
+                "{name}: accuracy {:.1}%, {} blunders",
+                self.accuracy(player),
+                self.blunders().filter(|turn| turn.player == player).count()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// The analysis as a JSON object: settings, per-player accuracy and the turns.
+    pub fn write_json(&self, names: [&str; PLAYER_COUNT], out: &mut impl Write) -> io::Result<()> {
+        let mut json = String::new();
+        let players: Vec<String> = names.iter().map(|name| json_string(name)).collect();
+        let accuracy: Vec<String> = (0..PLAYER_COUNT)
+            .map(|player| format!("{:.1}", self.accuracy(player)))
+            .collect();
+        write!(
+            json,
+            "{{\"players\":[{}],\"simulated\":{},\"blunder_threshold\":{:.1},\"accuracy\":[{}],\"turns\":[",
+            players.join(","),
+            self.simulated,
+            self.blunder_threshold,
+            accuracy.join(",")
+        )
+        .unwrap();
+        for (i, turn) in self.turns.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"turn\":{},\"player\":{},\"rack\":{},\"played\":{},\"withdrawn\":{},\"played_value\":{:.1},\"best\":{},\"best_value\":{:.1},\"equity_lost\":{:.1},\"blunder\":{}}}",
+                turn.turn + 1,
+                turn.player,
+                json_string(&turn.rack),
+                json_string(&turn.played),
+                turn.withdrawn,
+                turn.played_value,
+                turn.best.as_deref().map_or("null".to_string(), json_string),
+                turn.best_value,
+                turn.equity_lost,
+                turn.is_blunder
+            )
+            .unwrap();
+        }
+        json.push_str("]}");
+        writeln!(out, "{json}")
+    }
+
+    /// `game` in GCG with a note on every turn that missed the best play.
+    pub fn write_gcg(
+        &self,
+        game: &Game,
+        names: [&str; PLAYER_COUNT],
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let note = |index: usize| {
+            let turn = self.turns.get(index)?;
+            let best = turn.best.as_deref()?;
+            let blunder = if turn.is_blunder { ", blunder" } else { "" };
+            Some(format!(
+                "best {best} {:.1}, lost {:.1}{blunder}",
+                turn.best_value, turn.equity_lost
+            ))
+        };
+        game.write_annotated_gcg(names, note, out)
+    }
+}
+
+/// A pass or exchange as a move that places nothing and keeps the rest of the rack.
+fn keeping(rack: &Rack, exchanged: &[char]) -> EvaluatedMove {
+    let mut kept = rack.clone();
+    for &tile in exchanged {
+        kept.remove(tile)
+            .expect("exchanges were checked against the rack");
+    }
+    let crossword_move = CrosswordMove::from_arrays([EMPTY_TILE; RACK_SIZE], [0; RACK_SIZE], 0);
+    let leave = leave_after(&kept, &crossword_move);
+    EvaluatedMove {
+        crossword_move,
+        score: 0,
+        equity: leave_value(&leave),
+        leave,
+    }
+}
+
+/// The tiles not on the board nor on `rack`, from a full bag's `all_tiles`.
+fn unseen(all_tiles: &[char], board: &Board, rack: &Rack) -> Vec<char> {
+    let mut unseen = all_tiles.to_vec();
+    let occupied = board.occupied();
+    let on_board = occupied.iter().map(|index| rack_tile(board.get(index)));
+    let on_rack = rack.available_tiles().map(|(_, tile)| tile);
+    for tile in on_board.chain(on_rack) {
+        if let Some(i) = unseen.iter().position(|&t| t == tile) {
+            unseen.swap_remove(i);
+        }
+    }
+    unseen.sort_by_key(|&tile| (tile == BLANK, tile));
+    unseen
+}
+
+fn gcg_notation(notation: Option<&str>) -> String {
+    notation.map_or_else(String::new, gcg_play)
+}
+
+fn json_string(s: &str) -> String {
+    format!("\"{}\"", escape_json(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{MaxEquityPlayer, Player};
+
+    fn gaddag() -> Gaddag {
+        let words = [
+            "AT", "TA", "CAT", "ACT", "SAT", "CATS", "SCAT", "ACTS", "CAST",
+        ];
+        Gaddag::from_wordlist(&words.map(String::from).to_vec())
+    }
+
+    /// The first player gets every tile and makes one play, chosen from the plays sorted
+    /// by equity; the second player, with an empty rack, goes out by passing.
+    fn game(gaddag: &Gaddag, choose: fn(Vec<EvaluatedMove>) -> EvaluatedMove) -> Game {
+        let mut game = Game::with_bag(Bag::with_tiles("ACST".chars().collect(), 0), 0);
+        let mut moves = game.view().evaluated_moves(gaddag);
+        moves.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+        game.apply(Action::Play(choose(moves).crossword_move))
+            .unwrap();
+        game.apply(Action::Pass).unwrap();
+        game
+    }
+
+    #[test]
+    fn best_plays_lose_nothing() {
+        let gaddag = gaddag();
+        let mut game = Game::with_bag(Bag::with_tiles("ACST".chars().collect(), 0), 0);
+        let action = MaxEquityPlayer::new(&gaddag).choose(&game.view());
+        game.apply(action).unwrap();
+
+        let analysis = analyse(&gaddag, &game, &AnalysisConfig::default());
+        let turn = &analysis.turns[0];
+        assert_eq!((turn.equity_lost, turn.best.as_deref()), (0.0, None));
+        assert_eq!(turn.played_value, turn.best_value);
+        assert_eq!(analysis.accuracy(0), 100.0);
+    }
+
+    #[test]
+    fn worse_plays_lose_the_difference() {
+        let gaddag = gaddag();
+        let game = game(&gaddag, |mut moves| moves.pop().unwrap());
+
+        let analysis = analyse(&gaddag, &game, &AnalysisConfig::default());
+        let [worst, pass] = &analysis.turns[..] else {
+            panic!("two turns");
+        };
+        assert!(worst.equity_lost > 0.0 && worst.best.is_some());
+        assert_eq!(worst.equity_lost, worst.best_value - worst.played_value);
+        assert_eq!(worst.is_blunder, worst.equity_lost >= 10.0);
+        assert_eq!((pass.played.as_str(), pass.equity_lost), ("-", 0.0));
+
+        let strict = AnalysisConfig {
+            blunder_threshold: worst.equity_lost,
+            ..AnalysisConfig::default()
+        };
+        let analysis = analyse(&gaddag, &game, &strict);
+        assert_eq!(analysis.blunders().count(), 1);
+        assert_eq!(analysis.accuracy(0), 0.0);
+        assert_eq!(analysis.accuracy(1), 100.0);
+
+        let simulated = AnalysisConfig {
+            simulation: Some(SimulationConfig {
+                candidates: 2,
+                iterations: 2,
+                inference: None,
+            }),
+            ..strict
+        };
+        let analysis = analyse(&gaddag, &game, &simulated);
+        assert!(analysis.simulated);
+        assert!(analysis.turns.iter().all(|turn| turn.equity_lost >= 0.0));
+    }
+
+    #[test]
+    fn reports_mark_blunders() {
+        let gaddag = gaddag();
+        let game = game(&gaddag, |mut moves| moves.pop().unwrap());
+        let config = AnalysisConfig {
+            blunder_threshold: 0.1,
+            ..AnalysisConfig::default()
+        };
+        let analysis = analyse(&gaddag, &game, &config);
+        let names = ["first", "se\"cond"];
+        let write = |f: &dyn Fn(&mut Vec<u8>) -> io::Result<()>| {
+            let mut out = Vec::new();
+            f(&mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        let text = write(&|out| analysis.write_text(names, out));
+        assert!(text.lines().nth(1).unwrap().ends_with("blunder"));
+        assert!(text.contains("first: accuracy 0.0%, 1 blunders"));
+
+        let json = write(&|out| analysis.write_json(names, out));
+        assert!(json.starts_with(r#"{"players":["first","se\"cond"],"simulated":false,"#));
+        assert!(json.contains(r#""turns":[{"turn":1,"player":0,"#));
+        assert!(json.contains(r#""best":null"#));
+
+        let gcg = write(&|out| analysis.write_gcg(&game, names, out));
+        let note = gcg.lines().find(|line| line.starts_with("#note")).unwrap();
+        assert!(note.starts_with("#note best ") && note.ends_with("blunder"));
+    }
+}
@@ -0,0 +1,87 @@
+use crate::constants::{BLANK, INDEX_TO_CHAR, TILE_DATA};
+use crate::game::Rng;
+
+/// The tiles not yet drawn. Tiles are drawn at random from a seeded generator, so a game
+/// with the same seed and the same plays draws the same tiles.
+#[derive(Clone, Debug)]
+pub struct Bag {
+    tiles: Vec<char>,
+    rng: Rng,
+}
+
+impl Bag {
+    /// A full bag with the standard tile distribution (`TILE_DATA`), blanks as `?`.
+    pub fn new(seed: u64) -> Self {
+        let tiles = INDEX_TO_CHAR
+            .iter()
+            .flat_map(|&tile| std::iter::repeat_n(tile, TILE_DATA[&tile].count as usize))
+            .collect();
+        Self::with_tiles(tiles, seed)
+    }
+
+    /// A bag holding exactly `tiles`.
+    pub fn with_tiles(tiles: Vec<char>, seed: u64) -> Self {
+        Self {
+            tiles,
+            rng: Rng::new(seed),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// The tiles in the bag, in no particular order.
+    pub fn tiles(&self) -> &[char] {
+        &self.tiles
+    }
+
+    /// Draw up to `count` tiles, fewer when the bag runs out.
+    pub fn draw(&mut self, count: usize) -> Vec<char> {
+        let count = count.min(self.tiles.len());
+        (0..count)
+            .map(|_| {
+                let i = self.rng.below(self.tiles.len());
+                self.tiles.swap_remove(i)
+            })
+            .collect()
+    }
+
+    /// Put tiles back, e.g. after an exchange. Blanks may be given as lowercase letters.
+    pub fn put_back(&mut self, tiles: &[char]) {
+        self.tiles.extend(tiles.iter().map(|&tile| {
+            if tile.is_ascii_lowercase() {
+                BLANK
+            } else {
+                tile
+            }
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_bag_has_a_hundred_tiles() {
+        let mut bag = Bag::new(1);
+        assert_eq!(bag.len(), 100);
+        assert_eq!(bag.tiles().iter().filter(|&&t| t == '?').count(), 2);
+
+        let drawn = bag.draw(7);
+        assert_eq!((drawn.len(), bag.len()), (7, 93));
+        assert_eq!(Bag::new(1).draw(7), drawn);
+
+        bag.put_back(&drawn);
+        assert_eq!(bag.len(), 100);
+        assert_eq!(bag.draw(200).len(), 100);
+        assert!(bag.draw(1).is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::evaluation::SimulationConfig;
+use crate::game::{
+    CasualPlayer, Difficulty, MaxEquityPlayer, MaxScorePlayer, Player, RandomPlayer,
+    SimulationPlayer, WeakenedPlayer,
+};
+use crate::move_generation::Gaddag;
+
+/// The built-in players as plain settings, e.g. to pick them on the command line and build
+/// a fresh player for every game.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bot {
+    /// A random legal move, see `RandomPlayer`.
+    Random,
+    /// Always the highest-scoring move, see `MaxScorePlayer`.
+    HighestScore,
+    /// The move with the best score plus leave value, see `MaxEquityPlayer`.
+    Equity,
+    /// The move that does best against simulated opponent replies, see `SimulationPlayer`.
+    Simulation(SimulationConfig),
+    /// Sometimes the Nth-best move by equity, see `WeakenedPlayer`.
+    Weakened { mistake_rate: f64, max_rank: usize },
+    /// Score and randomness mixed by difficulty, see `CasualPlayer`. Without a vocabulary;
+    /// use `CasualPlayer::with_vocabulary` to restrict it.
+    Casual(Difficulty),
+}
+
+impl Bot {
+    /// A player with these settings; `seed` seeds its random choices, if any.
+    pub fn player<'a>(&self, gaddag: &'a Gaddag, seed: u64) -> Box<dyn Player + 'a> {
+        match *self {
+            Bot::Random => Box::new(RandomPlayer::new(gaddag, seed)),
+            Bot::HighestScore => Box::new(MaxScorePlayer::new(gaddag)),
+            Bot::Equity => Box::new(MaxEquityPlayer::new(gaddag)),
+            Bot::Simulation(config) => Box::new(SimulationPlayer::new(gaddag, config, seed)),
+            Bot::Weakened {
+                mistake_rate,
+                max_rank,
+            } => Box::new(WeakenedPlayer::new(gaddag, mistake_rate, max_rank, seed)),
+            Bot::Casual(difficulty) => Box::new(CasualPlayer::new(gaddag, difficulty, seed)),
+        }
+    }
+}
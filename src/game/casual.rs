@@ -0,0 +1,203 @@
+use crate::constants::RACK_SIZE;
+use crate::evaluation::EvaluatedMove;
+use crate::game::{Action, GameView, Player, Rng};
+use crate::move_generation::Gaddag;
+
+/// How well a `CasualPlayer` plays.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Difficulty {
+    /// From 0, a random move among the allowed ones, to 1, always the highest score.
+    pub strength: f64,
+    /// Most tiles played in one move.
+    pub max_tiles: usize,
+}
+
+impl Difficulty {
+    /// Preset for levels 1 (beginner) to 5 (full strength); other levels are clamped.
+    pub fn level(level: u8) -> Self {
+        let (strength, max_tiles) = match level.clamp(1, 5) {
+            1 => (0.2, 3),
+            2 => (0.4, 4),
+            3 => (0.6, 5),
+            4 => (0.8, 6),
+            _ => (1.0, RACK_SIZE),
+        };
+        Self {
+            strength,
+            max_tiles,
+        }
+    }
+}
+
+/// A bot for casual games: it only plays words from its vocabulary, places at most
+/// `max_tiles` tiles, and mixes score maximisation with randomness.
+///
+/// Moves are still generated and validated with the full lexicon; the vocabulary, e.g. a
+/// list of common words or the words up to some frequency rank, only narrows down which of
+/// them the bot considers. Every word a move forms, cross words included, has to be in it.
+pub struct CasualPlayer<'a> {
+    gaddag: &'a Gaddag,
+    vocabulary: Option<&'a Gaddag>,
+    difficulty: Difficulty,
+    rng: Rng,
+}
+
+impl<'a> CasualPlayer<'a> {
+    pub fn new(gaddag: &'a Gaddag, difficulty: Difficulty, seed: u64) -> Self {
+        Self {
+            gaddag,
+            vocabulary: None,
+            difficulty,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Only play words that are also in `vocabulary`.
+    pub fn with_vocabulary(mut self, vocabulary: &'a Gaddag) -> Self {
+        self.vocabulary = Some(vocabulary);
+        self
+    }
+
+    fn is_allowed(&self, view: &GameView, evaluated: &EvaluatedMove) -> bool {
+        let crossword_move = &evaluated.crossword_move;
+        if crossword_move.len() > self.difficulty.max_tiles {
+            return false;
+        }
+        let Some(vocabulary) = self.vocabulary else {
+            return true;
+        };
+        view.board
+            .formed_words(crossword_move)
+            .iter()
+            .all(|formed| vocabulary.is_word(&formed.word.to_ascii_uppercase()))
+    }
+}
+
+impl Player for CasualPlayer<'_> {
+    fn name(&self) -> String {
+        format!(
+            "casual:{}:{}",
+            (self.difficulty.strength * 100.0).round(),
+            self.difficulty.max_tiles
+        )
+    }
+
+    /// Each allowed move gets `strength * score / best score + (1 - strength) * noise`,
+    /// with uniform noise in `[0, 1)`, and the highest total is played.
+    fn choose(&mut self, view: &GameView) -> Action {
+        let moves: Vec<EvaluatedMove> = view
+            .evaluated_moves(self.gaddag)
+            .into_iter()
+            .filter(|m| self.is_allowed(view, m))
+            .collect();
+        let best_score = moves.iter().map(|m| m.score).max().unwrap_or(0).max(1) as f64;
+
+        let strength = self.difficulty.strength.clamp(0.0, 1.0);
+        let mut best: Option<(f64, EvaluatedMove)> = None;
+        for evaluated in moves {
+            let value = strength * evaluated.score as f64 / best_score
+                + (1.0 - strength) * self.rng.next_f64();
+            if best
+                .as_ref()
+                .is_none_or(|(best_value, _)| value > *best_value)
+            {
+                best = Some((value, evaluated));
+            }
+        }
+
+        match best {
+            Some((_, evaluated)) => Action::Play(evaluated.crossword_move),
+            None => view.fallback(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Bag, Game, MaxScorePlayer};
+
+    fn gaddag(words: &[&str]) -> Gaddag {
+        Gaddag::from_wordlist(&words.iter().map(|w| w.to_string()).collect())
+    }
+
+    fn game(rack: &str) -> Game {
+        Game::with_bag(Bag::with_tiles(rack.chars().collect(), 0), 0)
+    }
+
+    fn played(player: &mut impl Player, game: &Game) -> Option<String> {
+        let view = game.view();
+        match player.choose(&view) {
+            Action::Play(m) => {
+                let words: Vec<String> = view
+                    .board
+                    .formed_words(&m)
+                    .into_iter()
+                    .map(|formed| formed.word)
+                    .collect();
+                Some(words.join(" "))
+            }
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn full_strength_without_limits_maximises_score() {
+        let full = gaddag(&["ZA", "AT", "TA", "ZAT"]);
+        let game = game("ATZ");
+        let mut casual = CasualPlayer::new(&full, Difficulty::level(5), 1);
+        let mut max_score = MaxScorePlayer::new(&full);
+        assert_eq!(casual.choose(&game.view()), max_score.choose(&game.view()));
+    }
+
+    #[test]
+    fn only_words_from_the_vocabulary_are_played() {
+        let full = gaddag(&["ZA", "AT", "TA", "ZAT"]);
+        let common = gaddag(&["AT", "TA", "CAT"]);
+        let game = game("ATZ");
+
+        for seed in 0..20 {
+            let mut casual = CasualPlayer::new(&full, Difficulty::level(seed % 5 + 1), seed as u64)
+                .with_vocabulary(&common);
+            let word = played(&mut casual, &game).unwrap();
+            assert!(word == "AT" || word == "TA", "{word}");
+        }
+
+        let mut nothing_common =
+            CasualPlayer::new(&full, Difficulty::level(5), 0).with_vocabulary(&common);
+        assert_eq!(played(&mut nothing_common, &self::game("AZ")), None);
+    }
+
+    #[test]
+    fn long_moves_are_capped() {
+        let full = gaddag(&["AT", "TA", "RAT", "TAR", "STAR", "RATS", "TSAR", "ARTS"]);
+        let game = game("ARST");
+        let difficulty = Difficulty {
+            strength: 1.0,
+            max_tiles: 3,
+        };
+        let word = played(&mut CasualPlayer::new(&full, difficulty, 0), &game).unwrap();
+        assert_eq!(word.len(), 3);
+    }
+
+    #[test]
+    fn weaker_levels_play_lower_scores_on_average() {
+        let full = gaddag(&[
+            "AT", "TA", "RAT", "TAR", "STAR", "RATS", "TSAR", "ARTS", "ART",
+        ]);
+        let game = game("ARST");
+        let average = |level: u8| {
+            let total: u32 = (0..40)
+                .map(|seed| {
+                    let mut casual = CasualPlayer::new(&full, Difficulty::level(level), seed);
+                    match casual.choose(&game.view()) {
+                        Action::Play(m) => game.board().score_move(&m),
+                        _ => 0,
+                    }
+                })
+                .sum();
+            total as f64 / 40.0
+        };
+        assert!(average(1) < average(5));
+    }
+}
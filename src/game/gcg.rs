@@ -0,0 +1,156 @@
+use std::io::{self, Write};
+
+use crate::constants::PLAYER_COUNT;
+use crate::game::{Action, Game};
+
+impl Game {
+    /// Write the game in GCG format. Players are `p1`, `p2`, ... with `names` as their full
+    /// names; tiles played through are written as `.`. A withdrawn phony is followed by a
+    /// `--` line, a challenge bonus by a `(challenge)` line.
+    pub fn write_gcg(&self, names: [&str; PLAYER_COUNT], out: &mut impl Write) -> io::Result<()> {
+        self.write_annotated_gcg(names, |_| None, out)
+    }
+
+    /// `write_gcg` with a `#note` after every turn `note` has something to say about, given
+    /// the turn's index.
+    pub fn write_annotated_gcg(
+        &self,
+        names: [&str; PLAYER_COUNT],
+        note: impl Fn(usize) -> Option<String>,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        writeln!(out, "#character-encoding UTF-8")?;
+        for (player, name) in names.iter().enumerate() {
+            writeln!(out, "#player{} p{} {name}", player + 1, player + 1)?;
+        }
+
+        for (index, turn) in self.turns().iter().enumerate() {
+            let played = match (&turn.action, &turn.notation) {
+                (Action::Play(_), Some(notation)) => gcg_play(notation),
+                (Action::Exchange(tiles), _) => format!("-{}", tiles.iter().collect::<String>()),
+                _ => "-".to_string(),
+            };
+            writeln!(
+                out,
+                ">p{}: {} {played} {:+} {}",
+                turn.player + 1,
+                turn.rack,
+                turn.score,
+                turn.total
+            )?;
+
+            // A withdrawn phony takes its score back, a valid play may earn a bonus
+            match &turn.challenge {
+                Some(challenge) if challenge.is_phony() => writeln!(
+                    out,
+                    ">p{}: {} -- {:+} {}",
+                    turn.player + 1,
+                    turn.rack,
+                    challenge.score,
+                    challenge.total
+                )?,
+                Some(challenge) if challenge.score != 0 => writeln!(
+                    out,
+                    ">p{}: {} (challenge) {:+} {}",
+                    turn.player + 1,
+                    turn.rack,
+                    challenge.score,
+                    challenge.total
+                )?,
+                _ => {}
+            }
+            if let Some(note) = note(index) {
+                writeln!(out, "#note {note}")?;
+            }
+        }
+
+        for adjustment in self.adjustments() {
+            let rack = if adjustment.score < 0 {
+                format!("{} ", adjustment.tiles)
+            } else {
+                String::new()
+            };
+            writeln!(
+                out,
+                ">p{}: {rack}({}) {:+} {}",
+                adjustment.player + 1,
+                adjustment.tiles,
+                adjustment.score,
+                adjustment.total
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// GCG form of a play in standard notation: `8D HE(LL)O` becomes `8D HE..O`.
+pub(crate) fn gcg_play(notation: &str) -> String {
+    let mut playing_through = false;
+    notation
+        .chars()
+        .filter_map(|c| match c {
+            '(' | ')' => {
+                playing_through = c == '(';
+                None
+            }
+            _ if playing_through => Some('.'),
+            _ => Some(c),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CrosswordMove;
+    use crate::game::Bag;
+
+    #[test]
+    fn played_through_tiles_become_dots() {
+        assert_eq!(gcg_play("8D HE(LL)O"), "8D HE..O");
+        assert_eq!(gcg_play("H8 (A)Ts"), "H8 .Ts");
+    }
+
+    #[test]
+    fn game_is_written_turn_by_turn() {
+        let bag = Bag::with_tiles("CATQIZEAEIOUST".chars().collect(), 0);
+        let mut game = Game::with_bag(bag, 0);
+        let rack = game.rack(0).to_string();
+        let tiles: Vec<char> = rack.chars().take(3).collect();
+        game.apply(Action::Exchange(tiles)).unwrap_err();
+        game.apply(Action::Pass).unwrap();
+
+        let tiles: String = game
+            .rack(1)
+            .available_tiles()
+            .take(2)
+            .map(|(_, t)| t)
+            .collect();
+        let play = CrosswordMove::from_notation(&format!("8H {tiles}"), game.board()).unwrap();
+        game.apply(Action::Play(play)).unwrap();
+
+        let mut gcg = Vec::new();
+        game.write_gcg(["first", "second"], &mut gcg).unwrap();
+        let gcg = String::from_utf8(gcg).unwrap();
+        let lines: Vec<&str> = gcg.lines().collect();
+
+        assert_eq!(lines[1], "#player1 p1 first");
+        assert_eq!(lines[3], format!(">p1: {rack} - +0 0"));
+        assert!(lines[4].starts_with(&format!(">p2: {} 8H {tiles} +", game.turns()[1].rack)));
+    }
+
+    #[test]
+    fn withdrawn_phonies_take_their_score_back() {
+        let bag = Bag::with_tiles(vec!['A'; 14], 0);
+        let mut game = Game::with_bag(bag, 0);
+        let play = CrosswordMove::from_notation("8H AA", game.board()).unwrap();
+        game.apply(Action::Play(play)).unwrap();
+        game.challenge(&crate::move_generation::Gaddag::from_wordlist(&Vec::new()))
+            .unwrap();
+
+        let mut gcg = Vec::new();
+        game.write_gcg(["first", "second"], &mut gcg).unwrap();
+        let gcg = String::from_utf8(gcg).unwrap();
+        assert!(gcg.ends_with(">p1: AAAAAAA 8H AA +4 4\n>p1: AAAAAAA -- -4 0\n"));
+    }
+}
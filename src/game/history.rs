@@ -0,0 +1,303 @@
+use crate::error::Error;
+use crate::game::{Action, Challenge, Game};
+use crate::move_generation::Gaddag;
+
+/// Something done to a position in a `History`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Action(Action),
+    /// The last play was challenged.
+    Challenge,
+}
+
+/// A game together with every position it went through, for undo, redo and trying out
+/// alternatives.
+///
+/// Positions form a tree: going back and doing something else starts a new branch and
+/// keeps the old one, so it can be returned to with `follow`. The current line runs from
+/// the start through the current position and on along the branches last taken, which is
+/// where `redo` goes. Every position is stored whole, racks and bag included, so moving
+/// around never replays the game.
+#[derive(Clone, Debug)]
+pub struct History {
+    nodes: Vec<Node>,
+    current: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    game: Game,
+    parent: Option<usize>,
+    /// What led here from the parent.
+    step: Option<Step>,
+    children: Vec<usize>,
+    /// The child `redo` goes to: the one last visited.
+    next: Option<usize>,
+}
+
+impl History {
+    pub fn new(game: Game) -> Self {
+        Self {
+            nodes: vec![Node {
+                game,
+                parent: None,
+                step: None,
+                children: Vec::new(),
+                next: None,
+            }],
+            current: 0,
+        }
+    }
+
+    /// The current position.
+    pub fn game(&self) -> &Game {
+        &self.nodes[self.current].game
+    }
+
+    /// Play `action` from the current position, see `Game::apply`. Doing what was done
+    /// before from here goes down the same branch again; anything else starts a new one.
+    pub fn apply(&mut self, action: Action) -> Result<(), Error> {
+        let step = Step::Action(action.clone());
+        if self.follow_step(&step) {
+            return Ok(());
+        }
+        let mut game = self.game().clone();
+        game.apply(action)?;
+        self.push(game, step);
+        Ok(())
+    }
+
+    /// Challenge the last play from the current position, see `Game::challenge`.
+    pub fn challenge(&mut self, lexicon: &Gaddag) -> Result<Challenge, Error> {
+        let mut game = self.game().clone();
+        let challenge = game.challenge(lexicon)?;
+        if !self.follow_step(&Step::Challenge) {
+            self.push(game, Step::Challenge);
+        }
+        Ok(challenge)
+    }
+
+    /// Go back one step. Returns false at the start of the game.
+    pub fn undo(&mut self) -> bool {
+        match self.nodes[self.current].parent {
+            Some(parent) => {
+                self.nodes[parent].next = Some(self.current);
+                self.current = parent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Go forward one step along the current line. Returns false at its end.
+    pub fn redo(&mut self) -> bool {
+        match self.nodes[self.current].next {
+            Some(next) => {
+                self.current = next;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// What was done from the current position on each branch, in the order they were
+    /// first taken.
+    pub fn branches(&self) -> Vec<&Step> {
+        self.nodes[self.current]
+            .children
+            .iter()
+            .filter_map(|&child| self.nodes[child].step.as_ref())
+            .collect()
+    }
+
+    /// Go forward along the `index`th branch, which also becomes part of the current line.
+    /// Returns false if there is no such branch.
+    pub fn follow(&mut self, index: usize) -> bool {
+        match self.nodes[self.current].children.get(index) {
+            Some(&child) => {
+                self.nodes[self.current].next = Some(child);
+                self.current = child;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The positions of the current line, from the start of the game.
+    pub fn line(&self) -> Vec<&Game> {
+        self.line_nodes()
+            .into_iter()
+            .map(|node| &self.nodes[node].game)
+            .collect()
+    }
+
+    /// Number of steps from the start of the game to the current position.
+    pub fn position(&self) -> usize {
+        let mut steps = 0;
+        let mut node = self.current;
+        while let Some(parent) = self.nodes[node].parent {
+            node = parent;
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Go to the position after `turn` turns on the current line, after any challenge of
+    /// the last of them.
+    pub fn go_to_turn(&mut self, turn: usize) -> Result<(), Error> {
+        let line = self.line_nodes();
+        let Some(&node) = line
+            .iter()
+            .rev()
+            .find(|&&node| self.nodes[node].game.turns().len() == turn)
+        else {
+            return Err(Error::IllegalAction("the line has no such turn"));
+        };
+
+        // Coming back, `redo` should find the way along the same line
+        for pair in line.windows(2) {
+            self.nodes[pair[0]].next = Some(pair[1]);
+        }
+        self.current = node;
+        Ok(())
+    }
+
+    fn line_nodes(&self) -> Vec<usize> {
+        let mut line = vec![self.current];
+        while let Some(parent) = self.nodes[line[line.len() - 1]].parent {
+            line.push(parent);
+        }
+        line.reverse();
+        while let Some(next) = self.nodes[line[line.len() - 1]].next {
+            line.push(next);
+        }
+        line
+    }
+
+    /// Go to the child reached by `step`, if there is one.
+    fn follow_step(&mut self, step: &Step) -> bool {
+        let current = &self.nodes[self.current];
+        let child = current
+            .children
+            .iter()
+            .position(|&child| self.nodes[child].step.as_ref() == Some(step));
+        child.is_some_and(|index| self.follow(index))
+    }
+
+    fn push(&mut self, game: Game, step: Step) {
+        let node = self.nodes.len();
+        self.nodes.push(Node {
+            game,
+            parent: Some(self.current),
+            step: Some(step),
+            children: Vec::new(),
+            next: None,
+        });
+        self.nodes[self.current].children.push(node);
+        self.nodes[self.current].next = Some(node);
+        self.current = node;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::CrosswordMove;
+    use crate::game::Bag;
+
+    fn history() -> History {
+        let bag = Bag::with_tiles("AEIOURSTLNCDGMPBHAEIOUEEAAIIORST".chars().collect(), 7);
+        History::new(Game::with_bag(bag, 0))
+    }
+
+    fn play(history: &History, notation: &str) -> Action {
+        Action::Play(CrosswordMove::from_notation(notation, history.game().board()).unwrap())
+    }
+
+    /// Racks, bag, scores and turns, which undo and redo have to bring back.
+    fn state(game: &Game) -> (String, String, Vec<char>, [i32; 2], usize) {
+        (
+            game.rack(0).to_string(),
+            game.rack(1).to_string(),
+            game.bag().tiles().to_vec(),
+            game.scores(),
+            game.turns().len(),
+        )
+    }
+
+    #[test]
+    fn undo_and_redo_restore_whole_positions() {
+        let mut history = history();
+        let start = state(history.game());
+        let rack = history.game().rack(0).to_string();
+        let tiles: Vec<char> = rack.chars().take(2).collect();
+        history.apply(Action::Exchange(tiles)).unwrap();
+        history.apply(Action::Pass).unwrap();
+        let end = state(history.game());
+
+        assert!(history.undo() && history.undo());
+        assert!(!history.undo());
+        assert_eq!(state(history.game()), start);
+        assert_eq!(history.line().len(), 3);
+
+        assert!(history.redo() && history.redo());
+        assert!(!history.redo());
+        assert_eq!(state(history.game()), end);
+        assert_eq!(history.position(), 2);
+
+        history.go_to_turn(1).unwrap();
+        assert_eq!(history.game().turns().len(), 1);
+        assert!(history.go_to_turn(3).is_err());
+    }
+
+    #[test]
+    fn other_moves_start_a_branch() {
+        let mut history = history();
+        history.apply(Action::Pass).unwrap();
+        let passed = state(history.game());
+
+        // Undo the pass, exchange instead, and come back to the pass
+        history.undo();
+        let tile = history.game().rack(0).to_string().remove(0);
+        history.apply(Action::Exchange(vec![tile])).unwrap();
+        history.undo();
+        assert_eq!(
+            history.branches(),
+            [
+                &Step::Action(Action::Pass),
+                &Step::Action(Action::Exchange(vec![tile]))
+            ]
+        );
+        assert!(history.follow(0));
+        assert_eq!(state(history.game()), passed);
+
+        // Doing the same thing again goes down the existing branch
+        history.undo();
+        history.apply(Action::Pass).unwrap();
+        assert_eq!(history.nodes.len(), 3);
+        assert!(history.apply(play(&history, "8H XYZ")).is_err());
+        assert_eq!(history.position(), 1);
+    }
+
+    #[test]
+    fn challenges_are_steps() {
+        let mut history = history();
+        let rack = history.game().rack(0).to_string();
+        let word: String = rack.chars().take(2).collect();
+        history
+            .apply(play(&history, &format!("8H {word}")))
+            .unwrap();
+        let played = history.game().scores();
+
+        let challenge = history
+            .challenge(&Gaddag::from_wordlist(&Vec::new()))
+            .unwrap();
+        assert!(challenge.is_phony());
+        assert_eq!(history.game().scores(), [0, 0]);
+
+        history.go_to_turn(1).unwrap();
+        assert_eq!(history.position(), 2);
+        history.undo();
+        assert_eq!(history.game().scores(), played);
+    }
+}
@@ -0,0 +1,29 @@
+mod analysis;
+mod bag;
+mod bot;
+mod casual;
+mod gcg;
+mod history;
+mod player;
+mod players;
+mod rng;
+mod state;
+mod tournament;
+
+pub use analysis::{Analysis, AnalysisConfig, TurnAnalysis, analyse};
+pub use bag::Bag;
+pub use bot::Bot;
+pub use casual::{CasualPlayer, Difficulty};
+pub use history::{History, Step};
+pub use player::{GameView, Player};
+pub use players::{
+    MaxEquityPlayer, MaxScorePlayer, RandomPlayer, SimulationPlayer, WeakenedPlayer,
+};
+pub use rng::Rng;
+pub use state::{
+    Action, Challenge, ChallengeRule, Game, RackAdjustment, SCORELESS_TURNS_TO_END, Turn,
+};
+pub use tournament::{
+    GameRecord, Summary, TournamentConfig, play_game, run_tournament, run_tournament_with,
+    summarize, write_csv,
+};
@@ -0,0 +1,64 @@
+use crate::constants::{PLAYER_COUNT, RACK_SIZE};
+use crate::core::{Board, Rack};
+use crate::evaluation::{EvaluatedMove, evaluate_moves};
+use crate::game::{Action, Game, Turn};
+use crate::move_generation::{Gaddag, MoveGenerator};
+
+/// Chooses what to do on a turn. Implementations hold whatever they need to decide, such as
+/// a lexicon or a random number generator, and only see the game through a `GameView`.
+pub trait Player {
+    /// Name used in game records.
+    fn name(&self) -> String;
+
+    /// The action for the player to move in `view`.
+    fn choose(&mut self, view: &GameView) -> Action;
+}
+
+/// What the player to move knows about the game: everything except the bag's order and
+/// the other racks.
+#[derive(Clone, Debug)]
+pub struct GameView<'a> {
+    pub player: usize,
+    pub board: &'a Board,
+    pub rack: &'a Rack,
+    pub scores: [i32; PLAYER_COUNT],
+    /// Tiles in the bag or on other racks, alphabetically with blanks last.
+    pub unseen: Vec<char>,
+    pub bag_len: usize,
+    pub turns: &'a [Turn],
+}
+
+impl Game {
+    /// The game as the player to move sees it.
+    pub fn view(&self) -> GameView<'_> {
+        let player = self.to_move();
+        GameView {
+            player,
+            board: self.board(),
+            rack: self.rack(player),
+            scores: self.scores(),
+            unseen: self.unseen(player),
+            bag_len: self.bag().len(),
+            turns: self.turns(),
+        }
+    }
+}
+
+impl GameView<'_> {
+    /// Every legal play with its score, leave and equity, in generation order.
+    pub fn evaluated_moves(&self, gaddag: &Gaddag) -> Vec<EvaluatedMove> {
+        let moves =
+            MoveGenerator::new(gaddag).generate_all_moves(self.board, &mut self.rack.clone());
+        evaluate_moves(self.board, self.rack, moves)
+    }
+
+    /// What to do without a play: exchange the whole rack, or pass when the bag is too low
+    /// to exchange.
+    pub fn fallback(&self) -> Action {
+        if self.bag_len >= RACK_SIZE {
+            Action::Exchange(self.rack.available_tiles().map(|(_, tile)| tile).collect())
+        } else {
+            Action::Pass
+        }
+    }
+}
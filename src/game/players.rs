@@ -0,0 +1,337 @@
+use crate::evaluation::{EvaluatedMove, SimulationConfig, infer_leaves, simulate};
+use crate::game::{Action, GameView, Player, Rng};
+use crate::move_generation::Gaddag;
+
+/// Plays a legal move picked uniformly at random.
+pub struct RandomPlayer<'a> {
+    gaddag: &'a Gaddag,
+    rng: Rng,
+}
+
+impl<'a> RandomPlayer<'a> {
+    pub fn new(gaddag: &'a Gaddag, seed: u64) -> Self {
+        Self {
+            gaddag,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Player for RandomPlayer<'_> {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn choose(&mut self, view: &GameView) -> Action {
+        let mut moves = view.evaluated_moves(self.gaddag);
+        if moves.is_empty() {
+            return view.fallback();
+        }
+        let pick = self.rng.below(moves.len());
+        Action::Play(moves.swap_remove(pick).crossword_move)
+    }
+}
+
+/// Plays the highest-scoring move.
+pub struct MaxScorePlayer<'a> {
+    gaddag: &'a Gaddag,
+}
+
+impl<'a> MaxScorePlayer<'a> {
+    pub fn new(gaddag: &'a Gaddag) -> Self {
+        Self { gaddag }
+    }
+}
+
+impl Player for MaxScorePlayer<'_> {
+    fn name(&self) -> String {
+        "score".to_string()
+    }
+
+    fn choose(&mut self, view: &GameView) -> Action {
+        play_or_fallback(
+            view,
+            best_by(view.evaluated_moves(self.gaddag), |m| m.score as f32),
+        )
+    }
+}
+
+/// Plays the move with the best score plus leave value.
+pub struct MaxEquityPlayer<'a> {
+    gaddag: &'a Gaddag,
+}
+
+impl<'a> MaxEquityPlayer<'a> {
+    pub fn new(gaddag: &'a Gaddag) -> Self {
+        Self { gaddag }
+    }
+}
+
+impl Player for MaxEquityPlayer<'_> {
+    fn name(&self) -> String {
+        "equity".to_string()
+    }
+
+    fn choose(&mut self, view: &GameView) -> Action {
+        play_or_fallback(
+            view,
+            best_by(view.evaluated_moves(self.gaddag), |m| m.equity),
+        )
+    }
+}
+
+/// Plays the move that does best against simulated opponent replies, see `simulate`. With
+/// `config.inference` set, opponent racks are drawn from the leaves their last play
+/// suggests.
+pub struct SimulationPlayer<'a> {
+    gaddag: &'a Gaddag,
+    config: SimulationConfig,
+    rng: Rng,
+}
+
+impl<'a> SimulationPlayer<'a> {
+    pub fn new(gaddag: &'a Gaddag, config: SimulationConfig, seed: u64) -> Self {
+        Self {
+            gaddag,
+            config,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Player for SimulationPlayer<'_> {
+    fn name(&self) -> String {
+        let infer = if self.config.inference.is_some() {
+            "+infer"
+        } else {
+            ""
+        };
+        format!(
+            "sim:{}x{}{infer}",
+            self.config.candidates, self.config.iterations
+        )
+    }
+
+    fn choose(&mut self, view: &GameView) -> Action {
+        let opponent = self.config.inference.and_then(|config| {
+            let last = view
+                .turns
+                .last()
+                .filter(|turn| turn.player != view.player && !turn.is_withdrawn())?;
+            let Action::Play(played) = &last.action else {
+                return None;
+            };
+            let mut before = view.board.clone();
+            before.undo_move(played);
+            Some(infer_leaves(
+                self.gaddag,
+                &before,
+                played,
+                &view.unseen,
+                &config,
+                &mut self.rng,
+            ))
+        });
+
+        let moves = view.evaluated_moves(self.gaddag);
+        let best = simulate(
+            self.gaddag,
+            view.board,
+            moves,
+            &view.unseen,
+            opponent.as_ref(),
+            &self.config,
+            &mut self.rng,
+        )
+        .into_iter()
+        .next()
+        .map(|simulated| simulated.evaluated);
+        play_or_fallback(view, best)
+    }
+}
+
+/// An equity player for beginners: with probability `mistake_rate` it plays the Nth-best
+/// move instead of the best, N being uniform between 2 and `max_rank`.
+pub struct WeakenedPlayer<'a> {
+    gaddag: &'a Gaddag,
+    mistake_rate: f64,
+    max_rank: usize,
+    rng: Rng,
+}
+
+impl<'a> WeakenedPlayer<'a> {
+    pub fn new(gaddag: &'a Gaddag, mistake_rate: f64, max_rank: usize, seed: u64) -> Self {
+        Self {
+            gaddag,
+            mistake_rate,
+            max_rank,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Player for WeakenedPlayer<'_> {
+    fn name(&self) -> String {
+        format!(
+            "weak:{}:{}",
+            (self.mistake_rate * 100.0).round(),
+            self.max_rank
+        )
+    }
+
+    fn choose(&mut self, view: &GameView) -> Action {
+        let mut moves = view.evaluated_moves(self.gaddag);
+        if moves.is_empty() {
+            return view.fallback();
+        }
+        moves.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+
+        let worst = self.max_rank.min(moves.len());
+        let rank = if worst > 1 && self.rng.next_f64() < self.mistake_rate {
+            2 + self.rng.below(worst - 1)
+        } else {
+            1
+        };
+        Action::Play(moves.swap_remove(rank - 1).crossword_move)
+    }
+}
+
+/// The first move with the highest `key`, so ties go to generation order.
+fn best_by(
+    moves: Vec<EvaluatedMove>,
+    key: impl Fn(&EvaluatedMove) -> f32,
+) -> Option<EvaluatedMove> {
+    moves
+        .into_iter()
+        .reduce(|best, m| if key(&m) > key(&best) { m } else { best })
+}
+
+fn play_or_fallback(view: &GameView, best: Option<EvaluatedMove>) -> Action {
+    match best {
+        Some(best) => Action::Play(best.crossword_move),
+        None => view.fallback(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::BLANK;
+    use crate::core::CrosswordMove;
+    use crate::evaluation::InferenceConfig;
+    use crate::game::{Bag, Game};
+
+    fn gaddag() -> Gaddag {
+        let words = [
+            "AT", "TA", "CAT", "ACT", "SAT", "CATS", "SCAT", "ACTS", "CAST", "TAS",
+        ];
+        Gaddag::from_wordlist(&words.map(String::from).to_vec())
+    }
+
+    fn game(rack: &str) -> Game {
+        // The first player draws the whole bag
+        Game::with_bag(Bag::with_tiles(rack.chars().collect(), 0), 0)
+    }
+
+    fn played(player: &mut impl Player, game: &Game) -> String {
+        let view = game.view();
+        match player.choose(&view) {
+            Action::Play(m) => m.to_notation(view.board),
+            action => format!("{action:?}"),
+        }
+    }
+
+    #[test]
+    fn view_hides_the_other_rack() {
+        let game = Game::new(5, 1);
+        let view = game.view();
+        assert_eq!(view.player, 1);
+        assert_eq!(view.rack.to_string(), game.rack(1).to_string());
+        assert!(view.turns.is_empty());
+
+        // The other rack only shows up mixed with the bag among the unseen tiles
+        let mut hidden = game.bag().tiles().to_vec();
+        hidden.extend(game.rack(0).available_tiles().map(|(_, tile)| tile));
+        hidden.sort_by_key(|&tile| (tile == BLANK, tile));
+        assert_eq!(view.unseen, hidden);
+        assert_eq!(view.unseen.len(), 100 - 7);
+    }
+
+    #[test]
+    fn greedy_players_pick_their_best_move() {
+        let gaddag = gaddag();
+        let game = game("ACST");
+        let moves = game.view().evaluated_moves(&gaddag);
+        let value_of = |play: &str, key: fn(&EvaluatedMove) -> f32| {
+            let m = moves
+                .iter()
+                .find(|m| m.crossword_move.to_notation(game.board()) == play)
+                .unwrap();
+            key(m)
+        };
+        let best = |key: fn(&EvaluatedMove) -> f32| moves.iter().map(key).fold(f32::MIN, f32::max);
+
+        let score: fn(&EvaluatedMove) -> f32 = |m| m.score as f32;
+        let play = played(&mut MaxScorePlayer::new(&gaddag), &game);
+        assert_eq!(value_of(&play, score), best(score));
+
+        // Keeping the S is worth more than the points it would add
+        let equity: fn(&EvaluatedMove) -> f32 = |m| m.equity;
+        let play = played(&mut MaxEquityPlayer::new(&gaddag), &game);
+        assert_eq!(value_of(&play, equity), best(equity));
+        assert!(!play.contains('S'));
+    }
+
+    #[test]
+    fn random_and_weakened_players_play_legal_moves() {
+        let gaddag = gaddag();
+        let game = game("ACST");
+        let legal: Vec<String> = game
+            .view()
+            .evaluated_moves(&gaddag)
+            .iter()
+            .map(|m| m.crossword_move.to_notation(game.board()))
+            .collect();
+
+        let mut random = RandomPlayer::new(&gaddag, 1);
+        let mut always_wrong = WeakenedPlayer::new(&gaddag, 1.0, 3, 1);
+        let mut best = MaxEquityPlayer::new(&gaddag);
+        let best = played(&mut best, &game);
+        for _ in 0..20 {
+            assert!(legal.contains(&played(&mut random, &game)));
+            let weak = played(&mut always_wrong, &game);
+            assert!(legal.contains(&weak));
+            assert_ne!(weak, best);
+        }
+        let mut never_wrong = WeakenedPlayer::new(&gaddag, 0.0, 3, 1);
+        assert_eq!(played(&mut never_wrong, &game), best);
+    }
+
+    #[test]
+    fn inference_skips_withdrawn_phonies() {
+        let gaddag = Gaddag::from_wordlist(&vec!["AA".to_string()]);
+        let mut game = game("BBAA");
+        let phony = CrosswordMove::from_notation("8H BB", game.board()).unwrap();
+        game.apply(Action::Play(phony)).unwrap();
+        assert!(game.challenge(&gaddag).unwrap().is_phony());
+        assert!(game.turns()[0].is_withdrawn());
+
+        // The phony is no longer on the board to infer a leave from
+        let config = SimulationConfig {
+            inference: Some(InferenceConfig::default()),
+            ..SimulationConfig::default()
+        };
+        let mut player = SimulationPlayer::new(&gaddag, config, 1);
+        assert_eq!(player.choose(&game.view()), Action::Pass);
+    }
+
+    #[test]
+    fn players_without_a_play_exchange_or_pass() {
+        let gaddag = gaddag();
+        let game = game("QQ");
+        assert_eq!(
+            MaxScorePlayer::new(&gaddag).choose(&game.view()),
+            Action::Pass
+        );
+    }
+}
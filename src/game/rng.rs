@@ -0,0 +1,60 @@
+/// Small seeded random number generator (SplitMix64), so games, bags and simulations can be
+/// replayed from a seed on any platform.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`; `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        // Widening multiply instead of modulo avoids most of the bias for small `n`
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Seed for the `index`th of a series of games or runs derived from one seed.
+    pub fn derive_seed(seed: u64, index: u64) -> u64 {
+        Rng::new(seed ^ index.wrapping_mul(0xd134_2543_de82_ef95)).next_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let a: Vec<u64> = (0..8).map(|_| a.next_u64()).collect();
+        assert_eq!(a, (0..8).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(a, (0..8).map(|_| c.next_u64()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn ranges_are_respected() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 5];
+        for _ in 0..1000 {
+            seen[rng.below(5)] = true;
+            assert!((0.0..1.0).contains(&rng.next_f64()));
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
@@ -0,0 +1,612 @@
+use crate::constants::{BLANK, PLAYER_COUNT, RACK_SIZE, tile_score};
+use crate::core::{Board, CrosswordMove, Rack};
+use crate::error::Error;
+use crate::game::Bag;
+use crate::move_generation::Gaddag;
+
+/// Consecutive scoreless turns (passes, exchanges and zero-point plays) that end the game.
+pub const SCORELESS_TURNS_TO_END: usize = 6;
+
+/// What a player does on their turn.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Play(CrosswordMove),
+    /// Swap these rack tiles (`?` for blanks) for tiles from the bag.
+    Exchange(Vec<char>),
+    Pass,
+}
+
+/// A turn as it was played.
+#[derive(Clone, Debug)]
+pub struct Turn {
+    pub player: usize,
+    /// The rack before the turn, in canonical order.
+    pub rack: String,
+    pub action: Action,
+    /// Notation of a play on the board it was played on, e.g. `8D HE(LL)O`.
+    pub notation: Option<String>,
+    pub score: i32,
+    /// The player's score after the turn.
+    pub total: i32,
+    /// The challenge against the play, if there was one.
+    pub challenge: Option<Challenge>,
+}
+
+impl Turn {
+    /// Whether the turn was a phony taken back after a challenge, leaving the board as it
+    /// was before.
+    pub fn is_withdrawn(&self) -> bool {
+        self.challenge.as_ref().is_some_and(Challenge::is_phony)
+    }
+}
+
+/// What happens when a play is challenged, see `Game::challenge`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChallengeRule {
+    /// Phonies are withdrawn; challenging a valid play costs nothing.
+    #[default]
+    Single,
+    /// Phonies are withdrawn; a player who challenges a valid play loses their turn.
+    Double,
+    /// Phonies are withdrawn; a valid play earns its player these points (usually 5).
+    Penalty(i32),
+}
+
+/// The outcome of a challenge against a play.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Challenge {
+    pub challenger: usize,
+    /// Words formed by the play that are not in the lexicon. The play was withdrawn if there
+    /// are any.
+    pub phonies: Vec<String>,
+    /// Points for the player who made the play: minus the play's score when it was
+    /// withdrawn, the penalty when it stood.
+    pub score: i32,
+    /// That player's score after the challenge.
+    pub total: i32,
+}
+
+impl Challenge {
+    pub fn is_phony(&self) -> bool {
+        !self.phonies.is_empty()
+    }
+}
+
+/// Points for the tiles left on the racks when the game ends.
+///
+/// A player going out gets twice the value of the tiles on the other racks; when the game
+/// ends on scoreless turns, every player loses the value of their own rack.
+#[derive(Clone, Debug)]
+pub struct RackAdjustment {
+    pub player: usize,
+    pub tiles: String,
+    pub score: i32,
+    pub total: i32,
+}
+
+/// A game in progress: board, racks, scores and bag, and the turns so far.
+///
+/// `Game` enforces the rules about tiles (plays and exchanges must come from the rack,
+/// exchanges need a full rack's worth of tiles in the bag) but not the lexicon; checking
+/// words is up to whoever picks the action, or to the opponent through `challenge`.
+#[derive(Clone, Debug)]
+pub struct Game {
+    board: Board,
+    racks: [Rack; PLAYER_COUNT],
+    scores: [i32; PLAYER_COUNT],
+    bag: Bag,
+    to_move: usize,
+    turns: Vec<Turn>,
+    adjustments: Vec<RackAdjustment>,
+    scoreless_turns: usize,
+    is_over: bool,
+    challenge_rule: ChallengeRule,
+}
+
+impl Game {
+    /// A new game with a full bag drawn from `seed`. Players draw in turn order, starting
+    /// with `first_player`.
+    pub fn new(seed: u64, first_player: usize) -> Self {
+        Self::with_bag(Bag::new(seed), first_player)
+    }
+
+    pub fn with_bag(mut bag: Bag, first_player: usize) -> Self {
+        let mut racks: [Rack; PLAYER_COUNT] = std::array::from_fn(|_| Rack::empty());
+        for i in 0..PLAYER_COUNT {
+            let player = (first_player + i) % PLAYER_COUNT;
+            fill_rack(&mut racks[player], &mut bag);
+        }
+
+        Self {
+            board: Board::new(),
+            racks,
+            scores: [0; PLAYER_COUNT],
+            bag,
+            to_move: first_player,
+            turns: Vec::new(),
+            adjustments: Vec::new(),
+            scoreless_turns: 0,
+            is_over: false,
+            challenge_rule: ChallengeRule::default(),
+        }
+    }
+
+    pub fn with_challenge_rule(mut self, rule: ChallengeRule) -> Self {
+        self.challenge_rule = rule;
+        self
+    }
+
+    pub fn challenge_rule(&self) -> ChallengeRule {
+        self.challenge_rule
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn rack(&self, player: usize) -> &Rack {
+        &self.racks[player]
+    }
+
+    pub fn scores(&self) -> [i32; PLAYER_COUNT] {
+        self.scores
+    }
+
+    pub fn bag(&self) -> &Bag {
+        &self.bag
+    }
+
+    pub fn to_move(&self) -> usize {
+        self.to_move
+    }
+
+    pub fn turns(&self) -> &[Turn] {
+        &self.turns
+    }
+
+    pub fn adjustments(&self) -> &[RackAdjustment] {
+        &self.adjustments
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_over
+    }
+
+    /// The tiles `player` cannot see: the bag and the other racks, sorted alphabetically
+    /// with blanks last.
+    pub fn unseen(&self, player: usize) -> Vec<char> {
+        let mut unseen = self.bag.tiles().to_vec();
+        for (other, rack) in self.racks.iter().enumerate() {
+            if other != player {
+                unseen.extend(rack.available_tiles().map(|(_, tile)| tile));
+            }
+        }
+        unseen.sort_by_key(|&tile| (tile == BLANK, tile));
+        unseen
+    }
+
+    /// `player`'s score minus the best other score.
+    pub fn spread(&self, player: usize) -> i32 {
+        let best_other = (0..PLAYER_COUNT)
+            .filter(|&other| other != player)
+            .map(|other| self.scores[other])
+            .max()
+            .unwrap_or(0);
+        self.scores[player] - best_other
+    }
+
+    /// The player with the highest score, `None` on a tie.
+    pub fn winner(&self) -> Option<usize> {
+        (0..PLAYER_COUNT).find(|&player| self.spread(player) > 0)
+    }
+
+    /// Play `action` for the player to move. On error nothing changes.
+    ///
+    /// A play must be placed as `Board::try_check_placement` requires; the words it forms
+    /// are only checked if it is challenged.
+    pub fn apply(&mut self, action: Action) -> Result<(), Error> {
+        if self.is_over {
+            return Err(Error::IllegalAction("the game is over"));
+        }
+
+        let player = self.to_move;
+        let rack_before = self.racks[player].to_string();
+        let mut rack = self.racks[player].clone();
+
+        let (score, notation) = match &action {
+            Action::Play(crossword_move) => {
+                if crossword_move.is_empty() {
+                    return Err(Error::IllegalAction("a play needs at least one tile"));
+                }
+                self.board.try_check_placement(crossword_move)?;
+                for (tile, _) in crossword_move.iter() {
+                    rack.remove(rack_tile(tile))?;
+                }
+                let mut board = self.board.clone();
+                board.try_make_move(crossword_move)?;
+
+                let score = self.board.score_move(crossword_move) as i32;
+                let notation = crossword_move.to_notation(&self.board);
+                self.board = board;
+                fill_rack(&mut rack, &mut self.bag);
+                (score, Some(notation))
+            }
+            Action::Exchange(tiles) => {
+                if tiles.is_empty() {
+                    return Err(Error::IllegalAction("an exchange needs at least one tile"));
+                }
+                if self.bag.len() < RACK_SIZE {
+                    return Err(Error::IllegalAction(
+                        "exchanges need a full rack of tiles in the bag",
+                    ));
+                }
+                for &tile in tiles {
+                    rack.remove(tile)?;
+                }
+                // New tiles are drawn before the old ones go back
+                fill_rack(&mut rack, &mut self.bag);
+                self.bag.put_back(tiles);
+                (0, None)
+            }
+            Action::Pass => (0, None),
+        };
+
+        self.racks[player] = rack;
+        self.scores[player] += score;
+        self.turns.push(Turn {
+            player,
+            rack: rack_before,
+            action,
+            notation,
+            score,
+            total: self.scores[player],
+            challenge: None,
+        });
+        self.to_move = (player + 1) % PLAYER_COUNT;
+
+        if score == 0 {
+            self.scoreless_turns += 1;
+        } else {
+            self.scoreless_turns = 0;
+        }
+
+        if self.racks[player].is_empty() && self.bag.is_empty() {
+            self.went_out(player);
+        } else if self.scoreless_turns >= SCORELESS_TURNS_TO_END {
+            self.stalled();
+        }
+        Ok(())
+    }
+
+    /// The player to move challenges the last play, checking every word it formed against
+    /// `lexicon`. A phony is withdrawn: its tiles go back on the rack, the tiles drawn after
+    /// it go back in the bag and its score is taken off. A valid play is handled by the
+    /// challenge rule.
+    ///
+    /// Only the last turn can be challenged, once, and only if it was a play; the play that
+    /// ended the game by going out can still be challenged. Where the tiles went was checked
+    /// when the play was made, so a phony is always about its words.
+    pub fn challenge(&mut self, lexicon: &Gaddag) -> Result<Challenge, Error> {
+        let challenger = self.to_move;
+        let Some(turn) = self.turns.last() else {
+            return Err(Error::IllegalAction("there is no play to challenge"));
+        };
+        let Action::Play(crossword_move) = &turn.action else {
+            return Err(Error::IllegalAction("only plays can be challenged"));
+        };
+        if turn.challenge.is_some() {
+            return Err(Error::IllegalAction("the play was already challenged"));
+        }
+        let player = turn.player;
+        let went_out = self.is_over && self.racks[player].is_empty() && self.bag.is_empty();
+        if self.is_over && !went_out {
+            return Err(Error::IllegalAction("the game is over"));
+        }
+
+        let mut before = self.board.clone();
+        before.undo_move(crossword_move);
+        let phonies: Vec<String> = before
+            .formed_words(crossword_move)
+            .into_iter()
+            .map(|formed| formed.word.to_ascii_uppercase())
+            .filter(|word| !lexicon.is_word(word))
+            .collect();
+
+        let score = if !phonies.is_empty() {
+            let rack: Rack = turn.rack.parse()?;
+            let mut leave = rack.clone();
+            for (tile, _) in crossword_move.iter() {
+                leave.remove(rack_tile(tile))?;
+            }
+            let mut drawn: Vec<char> = self.racks[player]
+                .available_tiles()
+                .map(|(_, tile)| tile)
+                .collect();
+            for (_, tile) in leave.available_tiles() {
+                let i = drawn
+                    .iter()
+                    .position(|&t| t == tile)
+                    .expect("the rack still holds the leave");
+                drawn.swap_remove(i);
+            }
+
+            if went_out {
+                let adjustment = self.adjustments.pop().expect("going out adjusts the score");
+                self.scores[player] -= adjustment.score;
+                self.is_over = false;
+            }
+            self.board = before;
+            self.bag.put_back(&drawn);
+            self.racks[player] = rack;
+            -turn.score
+        } else if let ChallengeRule::Penalty(points) = self.challenge_rule {
+            points
+        } else {
+            0
+        };
+
+        self.scores[player] += score;
+        // A rack adjustment for going out on this play comes after the challenge
+        let adjusted: i32 = self
+            .adjustments
+            .iter()
+            .filter(|adjustment| adjustment.player == player)
+            .map(|adjustment| adjustment.score)
+            .sum();
+        let challenge = Challenge {
+            challenger,
+            phonies,
+            score,
+            total: self.scores[player] - adjusted,
+        };
+        let turn = self.turns.last_mut().expect("the challenged turn exists");
+        turn.challenge = Some(challenge.clone());
+
+        if challenge.is_phony() {
+            self.scoreless_turns = self
+                .turns
+                .iter()
+                .rev()
+                .take_while(|turn| turn.score + turn.challenge.as_ref().map_or(0, |c| c.score) == 0)
+                .count();
+            if self.scoreless_turns >= SCORELESS_TURNS_TO_END {
+                self.stalled();
+            }
+        } else if went_out {
+            for adjustment in &mut self.adjustments {
+                if adjustment.player == player {
+                    adjustment.total += score;
+                }
+            }
+        } else if self.challenge_rule == ChallengeRule::Double {
+            self.apply(Action::Pass)?;
+        }
+        Ok(challenge)
+    }
+
+    fn went_out(&mut self, player: usize) {
+        let mut tiles = String::new();
+        for (other, rack) in self.racks.iter().enumerate() {
+            if other != player {
+                tiles.push_str(&rack.to_string());
+            }
+        }
+        let score = 2 * rack_value(&tiles);
+        self.scores[player] += score;
+        self.adjustments.push(RackAdjustment {
+            player,
+            tiles,
+            score,
+            total: self.scores[player],
+        });
+        self.is_over = true;
+    }
+
+    fn stalled(&mut self) {
+        for player in 0..PLAYER_COUNT {
+            let tiles = self.racks[player].to_string();
+            let score = -rack_value(&tiles);
+            self.scores[player] += score;
+            self.adjustments.push(RackAdjustment {
+                player,
+                tiles,
+                score,
+                total: self.scores[player],
+            });
+        }
+        self.is_over = true;
+    }
+}
+
+/// The rack tile a tile on the board came from: blanks are lowercase on the board.
+pub(crate) fn rack_tile(tile: char) -> char {
+    if tile.is_ascii_lowercase() {
+        BLANK
+    } else {
+        tile
+    }
+}
+
+fn fill_rack(rack: &mut Rack, bag: &mut Bag) {
+    for tile in bag.draw(RACK_SIZE - rack.len) {
+        rack.add(tile)
+            .expect("a rack has room for the tiles it is missing");
+    }
+}
+
+fn rack_value(tiles: &str) -> i32 {
+    tiles.chars().map(tile_score).sum::<u32>() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{EMPTY_TILE, TOTAL_SIZE};
+
+    /// A bag that deals `racks` in order (first player first) and then holds `rest`.
+    fn game(racks: [&str; PLAYER_COUNT], rest: &str) -> Game {
+        let mut game = Game::with_bag(Bag::with_tiles(Vec::new(), 0), 0);
+        for (player, rack) in racks.iter().enumerate() {
+            game.racks[player] = rack.parse().unwrap();
+        }
+        game.bag = Bag::with_tiles(rest.chars().collect(), 0);
+        game
+    }
+
+    fn play(game: &Game, notation: &str) -> Action {
+        Action::Play(CrosswordMove::from_notation(notation, game.board()).unwrap())
+    }
+
+    #[test]
+    fn new_game_deals_racks() {
+        let game = Game::new(3, 1);
+        assert_eq!(game.to_move(), 1);
+        assert_eq!(game.bag().len(), 100 - 2 * RACK_SIZE);
+        assert_eq!(game.rack(0).len, RACK_SIZE);
+        assert_eq!(game.unseen(0).len(), 100 - RACK_SIZE);
+    }
+
+    #[test]
+    fn plays_score_and_refill_the_rack() {
+        let mut game = game(["CATSXYZ", "DOGEIAN"], "EEEEEEEEE");
+        game.apply(play(&game, "8G CAT")).unwrap();
+
+        assert_eq!(game.scores(), [10, 0]);
+        assert_eq!(game.rack(0).to_string(), "EEESXYZ");
+        assert_eq!(game.board().get(TOTAL_SIZE / 2), 'A');
+        assert_eq!(game.to_move(), 1);
+        assert_eq!(game.turns()[0].notation.as_deref(), Some("8G CAT"));
+
+        // Tiles that are not on the rack, and plays over occupied squares, change nothing
+        let err = game.apply(play(&game, "H7 C(A)T"));
+        assert!(matches!(err, Err(Error::TileNotOnRack('C'))));
+        let overlap = CrosswordMove::from_notation("8G D", &Board::new()).unwrap();
+        assert!(matches!(
+            game.apply(Action::Play(overlap)),
+            Err(Error::SquareOccupied(_))
+        ));
+        assert_eq!(game.turns().len(), 1);
+    }
+
+    #[test]
+    fn plays_must_be_placed_legally() {
+        let mut game = game(["AAAAAAA", "EEEEEEE"], "IIIIIIII");
+        let invalid = |game: &mut Game, notation: &str| {
+            let action = play(game, notation);
+            matches!(game.apply(action), Err(Error::InvalidMove(_)))
+        };
+
+        // The first play covers the centre with at least two tiles
+        assert!(invalid(&mut game, "1A AA"));
+        assert!(invalid(&mut game, "H8 A"));
+        game.apply(play(&game, "8G AA")).unwrap();
+
+        // Later plays touch the board, with no empty square between their tiles
+        assert!(invalid(&mut game, "1A EE"));
+        // E on G7 and I7, over the A on G8 with H7 left empty
+        let gapped = CrosswordMove::from_arrays(['E'; RACK_SIZE], [96, 98, 0, 0, 0, 0, 0], 2);
+        assert!(matches!(
+            game.apply(Action::Play(gapped)),
+            Err(Error::InvalidMove(_))
+        ));
+        assert_eq!(game.turns().len(), 1);
+        game.apply(play(&game, "7G EE")).unwrap();
+    }
+
+    #[test]
+    fn exchanges_need_a_full_bag() {
+        let mut game = game(["AAAAAAA", "EEEEEEE"], "IIIIIIII");
+        game.apply(Action::Exchange(vec!['A', 'A'])).unwrap();
+        assert_eq!(game.rack(0).to_string(), "AAAAAII");
+        assert_eq!(game.bag().len(), 8);
+        assert!(matches!(
+            game.apply(Action::Exchange(vec!['A'])),
+            Err(Error::TileNotOnRack('A'))
+        ));
+
+        let mut game = self::game(["AAAAAAA", "EEEEEEE"], "IIIIII");
+        assert!(matches!(
+            game.apply(Action::Exchange(vec!['A'])),
+            Err(Error::IllegalAction(_))
+        ));
+    }
+
+    #[test]
+    fn going_out_scores_the_other_rack_twice() {
+        let mut game = game(["CAT", "QI"], "");
+        game.apply(play(&game, "8G CAT")).unwrap();
+
+        assert!(game.is_over());
+        assert_eq!(game.scores(), [10 + 2 * 11, 0]);
+        assert_eq!(game.adjustments()[0].tiles, "IQ");
+        assert_eq!(game.winner(), Some(0));
+        assert!(game.apply(Action::Pass).is_err());
+    }
+
+    #[test]
+    fn phonies_are_withdrawn() {
+        let lexicon = Gaddag::from_wordlist(&vec!["CAT".to_string()]);
+        let mut game = game(["ACSTXYZ", "DOGEIAN"], "EEEEEEEEE");
+        game.apply(play(&game, "8G ACT")).unwrap();
+        let challenge = game.challenge(&lexicon).unwrap();
+
+        assert_eq!(challenge.phonies, ["ACT"]);
+        assert_eq!((challenge.challenger, challenge.score), (1, -10));
+        assert_eq!(game.scores(), [0, 0]);
+        assert_eq!(game.rack(0).to_string(), "ACSTXYZ");
+        assert_eq!(game.bag().len(), 9);
+        assert_eq!(game.board().get(TOTAL_SIZE / 2), EMPTY_TILE);
+        assert_eq!(game.to_move(), 1);
+        assert!(matches!(
+            game.challenge(&lexicon),
+            Err(Error::IllegalAction(_))
+        ));
+
+        // Going out on a phony does not end the game
+        let mut game = self::game(["ACT", "QI"], "");
+        game.apply(play(&game, "8G ACT")).unwrap();
+        assert!(game.is_over());
+        game.challenge(&lexicon).unwrap();
+        assert!(!game.is_over());
+        assert_eq!(game.scores(), [0, 0]);
+        assert!(game.adjustments().is_empty());
+    }
+
+    #[test]
+    fn valid_plays_follow_the_challenge_rule() {
+        let lexicon = Gaddag::from_wordlist(&vec!["CAT".to_string()]);
+        let challenged = |rule: ChallengeRule| {
+            let mut game = game(["ACTSXYZ", "DOGEIAN"], "EEEEEEEEE").with_challenge_rule(rule);
+            game.apply(play(&game, "8G CAT")).unwrap();
+            let challenge = game.challenge(&lexicon).unwrap();
+            assert!(!challenge.is_phony());
+            game
+        };
+
+        let single = challenged(ChallengeRule::Single);
+        assert_eq!((single.scores(), single.to_move()), ([10, 0], 1));
+
+        let double = challenged(ChallengeRule::Double);
+        assert_eq!((double.scores(), double.to_move()), ([10, 0], 0));
+        assert_eq!(double.turns()[1].action, Action::Pass);
+
+        let penalty = challenged(ChallengeRule::Penalty(5));
+        assert_eq!((penalty.scores(), penalty.to_move()), ([15, 0], 1));
+
+        let mut passed = game(["ACTSXYZ", "DOGEIAN"], "");
+        passed.apply(Action::Pass).unwrap();
+        assert!(passed.challenge(&lexicon).is_err());
+    }
+
+    #[test]
+    fn six_scoreless_turns_end_the_game() {
+        let mut game = game(["QI", "ZA"], "");
+        for _ in 0..SCORELESS_TURNS_TO_END {
+            assert!(!game.is_over());
+            game.apply(Action::Pass).unwrap();
+        }
+        assert!(game.is_over());
+        assert_eq!(game.scores(), [-11, -11]);
+        assert_eq!(game.winner(), None);
+    }
+}
@@ -0,0 +1,283 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::constants::PLAYER_COUNT;
+use crate::game::{Bot, Game, Player, Rng};
+use crate::move_generation::Gaddag;
+
+/// z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// How many games to play and how.
+#[derive(Clone, Copy, Debug)]
+pub struct TournamentConfig {
+    pub games: usize,
+    /// Seed of the whole tournament; every game gets its own seed derived from it.
+    pub seed: u64,
+    pub threads: usize,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            seed: 0,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
+/// A finished game of a tournament. Player 0 is always the first bot; who moved first
+/// alternates from game to game.
+#[derive(Clone, Debug)]
+pub struct GameRecord {
+    pub index: usize,
+    /// Names of the players, in player order.
+    pub players: [String; PLAYER_COUNT],
+    pub seed: u64,
+    pub first_player: usize,
+    pub game: Game,
+}
+
+impl GameRecord {
+    /// Final score of the first bot minus that of the second.
+    pub fn spread(&self) -> i32 {
+        self.game.spread(0)
+    }
+}
+
+/// Results of a tournament from the first bot's point of view. Ties count as half a win.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub games: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub ties: usize,
+    pub win_rate: f64,
+    /// Half-width of the 95% confidence interval of `win_rate`.
+    pub win_rate_margin: f64,
+    pub mean_spread: f64,
+    /// Half-width of the 95% confidence interval of `mean_spread`.
+    pub spread_margin: f64,
+}
+
+/// Play `config.games` games between `bots` on `config.threads` threads.
+///
+/// Game `i` uses the `i`th seed derived from `config.seed` and is started by player
+/// `i % 2`, so the results only depend on the seed, not on the number of threads. Records
+/// come back in game order.
+pub fn run_tournament(
+    gaddag: &Gaddag,
+    bots: &[Bot; PLAYER_COUNT],
+    config: &TournamentConfig,
+) -> Vec<GameRecord> {
+    run_tournament_with(config, |seed| {
+        std::array::from_fn(|player| {
+            bots[player].player(gaddag, Rng::derive_seed(seed, player as u64 + 1))
+        })
+    })
+}
+
+/// `run_tournament` for any players: `players(seed)` makes the players of the game with
+/// that seed, so every game starts from fresh players.
+pub fn run_tournament_with<'a, F>(config: &TournamentConfig, players: F) -> Vec<GameRecord>
+where
+    F: Fn(u64) -> [Box<dyn Player + 'a>; PLAYER_COUNT] + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let mut records: Vec<GameRecord> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..config.threads.max(1))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut records = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= config.games {
+                            return records;
+                        }
+                        let seed = Rng::derive_seed(config.seed, index as u64);
+                        let first_player = index % PLAYER_COUNT;
+                        let mut players = players(seed);
+                        records.push(GameRecord {
+                            index,
+                            players: std::array::from_fn(|player| players[player].name()),
+                            seed,
+                            first_player,
+                            game: play_game(&mut players, seed, first_player),
+                        });
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("tournament worker panicked"))
+            .collect()
+    });
+
+    records.sort_by_key(|record| record.index);
+    records
+}
+
+/// Play one game to the end; `players[i]` plays for player `i`.
+pub fn play_game(
+    players: &mut [Box<dyn Player + '_>; PLAYER_COUNT],
+    seed: u64,
+    first_player: usize,
+) -> Game {
+    let mut game = Game::new(seed, first_player);
+    while !game.is_over() {
+        let action = players[game.to_move()].choose(&game.view());
+        game.apply(action)
+            .expect("players only choose actions that are legal in the game");
+    }
+    game
+}
+
+pub fn summarize(records: &[GameRecord]) -> Summary {
+    let spreads: Vec<i32> = records.iter().map(GameRecord::spread).collect();
+    Summary::from_spreads(&spreads)
+}
+
+impl Summary {
+    /// Summary of games that ended with the first bot ahead by `spreads`.
+    pub fn from_spreads(spreads: &[i32]) -> Self {
+        let games = spreads.len();
+        let wins = spreads.iter().filter(|&&s| s > 0).count();
+        let losses = spreads.iter().filter(|&&s| s < 0).count();
+        let ties = games - wins - losses;
+
+        let n = games.max(1) as f64;
+        let win_rate = (wins as f64 + ties as f64 / 2.0) / n;
+        let mean_spread = spreads.iter().map(|&s| s as f64).sum::<f64>() / n;
+        let variance = if games > 1 {
+            spreads
+                .iter()
+                .map(|&s| (s as f64 - mean_spread).powi(2))
+                .sum::<f64>()
+                / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            games,
+            wins,
+            losses,
+            ties,
+            win_rate,
+            win_rate_margin: Z_95 * (win_rate * (1.0 - win_rate) / n).sqrt(),
+            mean_spread,
+            spread_margin: Z_95 * (variance / n).sqrt(),
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} games: {} wins, {} losses, {} ties\n\
+             win rate {:.1}% ± {:.1}%\n\
+             mean spread {:+.1} ± {:.1} (95% confidence)",
+            self.games,
+            self.wins,
+            self.losses,
+            self.ties,
+            100.0 * self.win_rate,
+            100.0 * self.win_rate_margin,
+            self.mean_spread,
+            self.spread_margin
+        )
+    }
+}
+
+/// One line per game: index, seed, who went first, both scores, spread and turns.
+pub fn write_csv(records: &[GameRecord], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "game,seed,first_player,score_a,score_b,spread,turns")?;
+    for record in records {
+        let [a, b] = record.game.scores();
+        writeln!(
+            out,
+            "{},{},{},{a},{b},{},{}",
+            record.index,
+            record.seed,
+            record.first_player,
+            record.spread(),
+            record.game.turns().len()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaddag() -> Gaddag {
+        let words = [
+            "AA", "AB", "AD", "AE", "AG", "AH", "AI", "AL", "AM", "AN", "AR", "AS", "AT", "AW",
+            "AX", "AY", "BA", "BE", "BI", "BO", "BY", "DA", "DE", "DO", "ED", "EF", "EH", "EL",
+            "EM", "EN", "ER", "ES", "EX", "FA", "FE", "GO", "HA", "HE", "HI", "HM", "HO", "ID",
+            "IF", "IN", "IS", "IT", "JO", "KA", "KI", "LA", "LI", "LO", "MA", "ME", "MI", "MO",
+            "MU", "MY", "NA", "NE", "NO", "NU", "OD", "OE", "OF", "OH", "OI", "OM", "ON", "OP",
+            "OR", "OS", "OW", "OX", "OY", "PA", "PE", "PI", "QI", "RE", "SH", "SI", "SO", "TA",
+            "TI", "TO", "UH", "UM", "UN", "UP", "US", "UT", "WE", "WO", "XI", "XU", "YA", "YE",
+            "YO", "ZA",
+        ];
+        Gaddag::from_wordlist(&words.map(String::from).to_vec())
+    }
+
+    #[test]
+    fn results_do_not_depend_on_threads() {
+        let gaddag = gaddag();
+        let bots = [Bot::Equity, Bot::HighestScore];
+        let config = TournamentConfig {
+            games: 4,
+            seed: 9,
+            threads: 1,
+        };
+        let single = run_tournament(&gaddag, &bots, &config);
+        let threaded = run_tournament(
+            &gaddag,
+            &bots,
+            &TournamentConfig {
+                threads: 3,
+                ..config
+            },
+        );
+
+        assert_eq!(single.len(), 4);
+        assert!(single.iter().all(|r| r.game.is_over()));
+        assert_eq!(
+            single
+                .iter()
+                .map(|r| (r.first_player, r.game.scores()))
+                .collect::<Vec<_>>(),
+            threaded
+                .iter()
+                .map(|r| (r.first_player, r.game.scores()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(single[0].first_player, 0);
+        assert_eq!(single[1].first_player, 1);
+
+        let mut csv = Vec::new();
+        write_csv(&single, &mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap().lines().count(), 5);
+    }
+
+    #[test]
+    fn summary_counts_ties_as_half() {
+        let summary = Summary::from_spreads(&[10, -20, 0, 30]);
+        assert_eq!((summary.wins, summary.losses, summary.ties), (2, 1, 1));
+        assert_eq!(summary.win_rate, 0.625);
+        assert_eq!(summary.mean_spread, 5.0);
+        // Sample standard deviation of the spreads is sqrt(1300 / 3)
+        let margin = 1.96 * (1300.0f64 / 3.0 / 4.0).sqrt();
+        assert!((summary.spread_margin - margin).abs() < 1e-9);
+        assert!((summary.win_rate_margin - 1.96 * (0.625f64 * 0.375 / 4.0).sqrt()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod constants;
+pub mod core;
+pub mod error;
+pub mod evaluation;
+pub mod game;
+pub mod move_generation;
+pub mod render;
+
+pub use error::Error;
@@ -1,28 +1,30 @@
-mod constants;
-mod core;
-mod move_generation;
+mod cli;
 
-use crate::constants::BoardPosition;
-use crate::core::{Board, CrosswordMove};
+use std::process::ExitCode;
 
-// Currently just a simple make a move example
-fn main() {
-    let mut board = Board::new();
+use crate::cli::Command;
 
-    // Example
-    let tiles = ['H', 'E', 'L', 'L', 'O', ' ', ' '];
-    let positions: [BoardPosition; 7] = [49, 50, 51, 52, 53, 0, 0];
-
-    let crossword_move = CrosswordMove::from_arrays(tiles, positions, 5);
-
-    board.make_move(&crossword_move);
+fn main() -> ExitCode {
+    let command = match Command::parse(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {message}");
+            eprintln!("{}", cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
 
-    // Print the board row by row
-    for row in 0..crate::constants::BOARD_SIZE {
-        for col in 0..crate::constants::BOARD_SIZE {
-            let idx = row * crate::constants::BOARD_SIZE + col;
-            print!("{}", board.get(idx));
+    match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
         }
-        println!();
+        Command::Run(options) => match cli::run(&options) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(message) => {
+                eprintln!("error: {message}");
+                ExitCode::FAILURE
+            }
+        },
     }
 }
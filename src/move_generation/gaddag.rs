@@ -1,6 +1,19 @@
 #![allow(dead_code)]
 
-use crate::constants::{PIVOT, PIVOT_BIT_IDX, TileBitboard, get_index};
+mod hooks;
+mod queries;
+mod set_ops;
+mod words;
+
+pub use hooks::Hooks;
+pub use set_ops::{LexiconDiff, SourceFlags};
+pub use words::{WordStats, Words};
+
+use std::fs;
+use std::path::Path;
+
+use crate::constants::{PIVOT, PIVOT_BIT_IDX, TileBitboard, get_index, try_get_index};
+use crate::error::Error;
 
 /// A GADDAG trie structure for efficient word lookup and Scrabble-like move generation.
 pub struct Gaddag {
@@ -8,7 +21,8 @@ pub struct Gaddag {
 }
 
 /// # Fields
-/// - 'is_word': Indicates if the node, and thereby the path to the node, is a word.
+/// - 'sources': Non-zero if the node, and thereby the path to the node, is a word. Each bit
+///   is a lexicon the word comes from; lexicons built from a single list only use bit 0.
 /// - 'children': An array of optional child nodes, each corresponding to a valid
 ///   tile character. The tail node is the pivot (represented by '>') from which
 ///   suffixes are stored backwards.
@@ -17,9 +31,9 @@ pub struct Gaddag {
 /// - Letters at the start of the gaddag (from root) are all in reverse order. There is
 ///   no pivot from root.
 pub struct GaddagNode {
-    is_word: bool,
+    sources: SourceFlags,
     children_mask: TileBitboard,
-    children_ptrs: Vec<Box<GaddagNode>>,
+    children_ptrs: Vec<GaddagNode>,
 }
 
 impl Gaddag {
@@ -33,6 +47,43 @@ impl Gaddag {
         gaddag
     }
 
+    /// Checked `from_wordlist`: every word must be non-empty and only use the letters `A`-`Z`.
+    /// Errors report the 1-based position of the word in `words` as the line.
+    pub fn try_from_wordlist(words: &Vec<String>) -> Result<Self, Error> {
+        for (i, word) in words.iter().enumerate() {
+            if word.is_empty() || !word.chars().all(|c| c.is_ascii_uppercase()) {
+                return Err(Error::InvalidWord {
+                    line: i + 1,
+                    word: word.clone(),
+                });
+            }
+        }
+        Ok(Self::from_wordlist(words))
+    }
+
+    /// Load a word list with one word per line. Words are uppercased and blank lines are
+    /// skipped; any other character than `A`-`Z` is reported as an invalid word.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let contents = fs::read_to_string(path)?;
+        let mut words = Vec::new();
+
+        for (line_no, line) in contents.lines().enumerate() {
+            let word = line.trim().to_ascii_uppercase();
+            if word.is_empty() {
+                continue;
+            }
+            if !word.chars().all(|c| c.is_ascii_uppercase()) {
+                return Err(Error::InvalidWord {
+                    line: line_no + 1,
+                    word: line.trim().to_string(),
+                });
+            }
+            words.push(word);
+        }
+
+        Ok(Self::from_wordlist(&words))
+    }
+
     pub fn get_root(&self) -> &GaddagNode {
         &self.root
     }
@@ -65,14 +116,18 @@ impl Gaddag {
 impl GaddagNode {
     fn new() -> Self {
         Self {
-            is_word: false,
+            sources: 0,
             children_mask: 0,
             children_ptrs: Vec::new(),
         }
     }
 
     // Creates paths that are then inserted
-    fn insert_gaddag(&mut self, word: &String) {
+    fn insert_gaddag(&mut self, word: &str) {
+        self.insert_gaddag_with_sources(word, 1);
+    }
+
+    fn insert_gaddag_with_sources(&mut self, word: &str, sources: SourceFlags) {
         let chars: Vec<char> = word.chars().collect();
 
         for i in 0..=chars.len() {
@@ -91,11 +146,15 @@ impl GaddagNode {
                 path.push(c);
             }
 
-            self.insert_path(&path);
+            self.insert_path_with_sources(&path, sources);
         }
     }
 
     pub fn insert_path(&mut self, path: &[char]) {
+        self.insert_path_with_sources(path, 1);
+    }
+
+    fn insert_path_with_sources(&mut self, path: &[char], sources: SourceFlags) {
         let mut node = self;
 
         for (i, &tile) in path.iter().enumerate() {
@@ -119,7 +178,7 @@ impl GaddagNode {
                 node = &mut node.children_ptrs[pos];
             } else {
                 // Create new child node
-                let new_node = Box::new(GaddagNode::new());
+                let new_node = GaddagNode::new();
 
                 // Insert at the correct position to maintain mask order
                 node.children_ptrs.insert(pos, new_node);
@@ -131,18 +190,19 @@ impl GaddagNode {
 
             // If this is the last character in the path, mark the node as a word.
             // Do this for both newly-created and existing nodes so insertion order
-            // doesn't affect the 'is_word' flag.
+            // doesn't affect the word flags.
             if i == path.len() - 1 {
-                node.is_word = true;
+                node.sources |= sources;
             }
         }
     }
 
+    /// The child for `tile`, `None` if there is none or `tile` is not a tile at all.
     pub fn get_child(&self, tile: char) -> Option<&GaddagNode> {
         let idx = if tile == PIVOT {
             PIVOT_BIT_IDX
         } else {
-            get_index(tile) as u32
+            try_get_index(tile).ok()? as u32
         };
         let bit: TileBitboard = (1 as TileBitboard) << idx;
 
@@ -157,7 +217,12 @@ impl GaddagNode {
     }
 
     pub fn is_word(&self) -> bool {
-        self.is_word
+        self.sources != 0
+    }
+
+    /// The lexicons the word ending at this node comes from, 0 if it is not a word.
+    pub fn sources(&self) -> SourceFlags {
+        self.sources
     }
 }
 
@@ -230,7 +295,7 @@ mod tests {
     fn pivot_paths_from_insert_gaddag() {
         // Insert the word using insert_gaddag which creates paths containing the pivot
         let mut root = GaddagNode::new();
-        root.insert_gaddag(&"CAT".to_string());
+        root.insert_gaddag("CAT");
 
         // i = 0 path: [PIVOT, 'C', 'A', 'T']
         let p0 = traverse(&root, &[PIVOT, 'C', 'A', 'T']).expect("pivot-start path");
@@ -278,5 +343,27 @@ mod tests {
         assert!(!g.is_word("DO"), "DO is a prefix but not a word");
         assert!(!g.is_word("ACT"), "ACT is not in the wordlist");
         assert!(!g.is_word(""), "Empty string should not be found");
+        assert!(!g.is_word("C4T"), "Non-tiles should not be found");
+    }
+
+    #[test]
+    fn checked_loading_reports_invalid_words() {
+        let words = vec!["CAT".to_string(), "DOG".to_string()];
+        assert!(Gaddag::try_from_wordlist(&words).unwrap().is_word("DOG"));
+
+        let words = vec!["CAT".to_string(), "D0G".to_string()];
+        match Gaddag::try_from_wordlist(&words) {
+            Err(Error::InvalidWord { line, word }) => assert_eq!((line, word.as_str()), (2, "D0G")),
+            _ => panic!("D0G should be rejected"),
+        }
+        let words = vec![String::new()];
+        assert!(matches!(
+            Gaddag::try_from_wordlist(&words),
+            Err(Error::InvalidWord { line: 1, .. })
+        ));
+        assert!(matches!(
+            Gaddag::from_file("/nonexistent/words.txt"),
+            Err(Error::Io(_))
+        ));
     }
 }
@@ -0,0 +1,132 @@
+use std::io;
+
+use crate::constants::{INDEX_TO_CHAR, PIVOT, TileBitboard, get_index};
+use crate::move_generation::gaddag::queries::letter_children;
+use crate::move_generation::gaddag::{Gaddag, GaddagNode};
+
+/// The letters that can be added in front of and after a word. Bit `get_index(c)` is set when
+/// letter `c` hooks the word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hooks {
+    pub front: TileBitboard,
+    pub back: TileBitboard,
+}
+
+impl Hooks {
+    pub fn front_letters(&self) -> Vec<char> {
+        letters(self.front)
+    }
+
+    pub fn back_letters(&self) -> Vec<char> {
+        letters(self.back)
+    }
+}
+
+impl Gaddag {
+    /// Front and back hooks of `word`. Both sets are empty when nothing hooks the word, and
+    /// also for input with characters outside `A`-`Z`.
+    pub fn hooks(&self, word: &str) -> Hooks {
+        let mut hooks = Hooks::default();
+        let Some(node) = self.reversed_word_node(word) else {
+            return hooks;
+        };
+
+        // cW is stored as reverse(W) c >
+        for (c, child) in letter_children(node) {
+            if child.get_child(PIVOT).is_some_and(GaddagNode::is_word) {
+                hooks.front |= 1 << get_index(c);
+            }
+        }
+
+        // Wc is stored as reverse(W) > c
+        if let Some(pivot) = node.get_child(PIVOT) {
+            for (c, child) in letter_children(pivot) {
+                if child.is_word() {
+                    hooks.back |= 1 << get_index(c);
+                }
+            }
+        }
+
+        hooks
+    }
+
+    /// Letters that can be put in front of `word` to make another word, e.g. S for CAT.
+    pub fn front_hooks(&self, word: &str) -> Vec<char> {
+        self.hooks(word).front_letters()
+    }
+
+    /// Letters that can be put after `word` to make another word, e.g. S for CAT.
+    pub fn back_hooks(&self, word: &str) -> Vec<char> {
+        self.hooks(word).back_letters()
+    }
+
+    /// Write the hooks of every word in the lexicon, one tab-separated line per word in
+    /// alphabetical order: front hooks, the word, back hooks (e.g. `S\tCAT\tS`).
+    pub fn write_hooks_table(&self, out: &mut impl io::Write) -> io::Result<()> {
+        for word in self.word_list() {
+            let hooks = self.hooks(&word);
+            let front: String = hooks.front_letters().into_iter().collect();
+            let back: String = hooks.back_letters().into_iter().collect();
+            writeln!(out, "{front}\t{word}\t{back}")?;
+        }
+        Ok(())
+    }
+}
+
+fn letters(bitboard: TileBitboard) -> Vec<char> {
+    INDEX_TO_CHAR
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| bitboard & (1 << i) != 0)
+        .map(|(_, &c)| c)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaddag() -> Gaddag {
+        let words = [
+            "AT", "CAT", "CATS", "EAT", "SCAT", "TA", "TAB", "TAE", "TAT",
+        ];
+        Gaddag::from_wordlist(&words.iter().map(|w| w.to_string()).collect())
+    }
+
+    #[test]
+    fn hook_sets_are_bitboards() {
+        let g = gaddag();
+        let hooks = g.hooks("CAT");
+        assert_eq!(hooks.front, 1 << get_index('S'));
+        assert_eq!(hooks.back, 1 << get_index('S'));
+
+        let ta = g.hooks("ta");
+        assert_eq!(ta.front_letters(), Vec::<char>::new());
+        assert_eq!(ta.back_letters(), vec!['B', 'E', 'T']);
+        assert_eq!(g.front_hooks("AT"), vec!['C', 'E', 'T']);
+        assert_eq!(g.back_hooks("AT"), Vec::<char>::new());
+    }
+
+    #[test]
+    fn non_words_and_invalid_input_have_no_hooks() {
+        let g = gaddag();
+        assert_eq!(g.hooks("SCATS"), Hooks::default());
+        assert_eq!(g.hooks("C4T"), Hooks::default());
+        // Hooks are looked up for any letter sequence, not only for words
+        assert_eq!(g.front_hooks("CA"), Vec::<char>::new());
+    }
+
+    #[test]
+    fn hooks_table_lists_every_word() {
+        let g = gaddag();
+        let mut out = Vec::new();
+        g.write_hooks_table(&mut out).unwrap();
+        let table = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[0], "CET\tAT\t");
+        assert_eq!(lines[1], "S\tCAT\tS");
+        assert_eq!(lines[5], "\tTA\tBET");
+    }
+}
@@ -0,0 +1,276 @@
+use crate::constants::{BLANK, INDEX_TO_CHAR, PIVOT, UNIQUE_TILES, get_index};
+use crate::move_generation::gaddag::{Gaddag, GaddagNode};
+
+/// Matches any single letter in `Gaddag::matching_pattern`.
+pub const PATTERN_ANY_LETTER: char = '?';
+/// Matches any run of letters, including none, in `Gaddag::matching_pattern`.
+pub const PATTERN_ANY_RUN: char = '*';
+
+// Word queries for study tools. Every word is stored from the root as the pivot followed by
+// the word read forwards, so walking the pivot child gives a plain trie in alphabetical order.
+// Queries given characters outside `A`-`Z` (and their wildcards) find nothing.
+impl Gaddag {
+    /// Words that use every tile of `rack` exactly once. `?` is a blank.
+    pub fn anagrams(&self, rack: &str) -> Vec<String> {
+        self.words_from_rack(rack, true)
+    }
+
+    /// Words that can be made from any of the tiles of `rack`. `?` is a blank.
+    pub fn subanagrams(&self, rack: &str) -> Vec<String> {
+        self.words_from_rack(rack, false)
+    }
+
+    /// Words matching `pattern`, where `?` stands for one letter and `*` for any number of
+    /// letters, e.g. `C?T*` matches CAT, CUT and CATS.
+    pub fn matching_pattern(&self, pattern: &str) -> Vec<String> {
+        let pattern: Vec<char> = pattern.chars().map(|c| c.to_ascii_uppercase()).collect();
+        let valid = pattern
+            .iter()
+            .all(|&c| c.is_ascii_uppercase() || c == PATTERN_ANY_LETTER || c == PATTERN_ANY_RUN);
+        let Some(start) = self.get_root().get_child(PIVOT).filter(|_| valid) else {
+            return Vec::new();
+        };
+
+        let mut words = Vec::new();
+        collect_pattern(start, &pattern, &mut String::new(), &mut words);
+        // Stars can match the same word in more than one way
+        words.sort();
+        words.dedup();
+        words
+    }
+
+    /// Words that contain `fragment` anywhere, in alphabetical order.
+    pub fn containing(&self, fragment: &str) -> Vec<String> {
+        let Some(fragment) = normalize_letters(fragment) else {
+            return Vec::new();
+        };
+
+        // A word xFy is stored as reverse(xF) > y, so walking the reversed fragment from the
+        // root leaves us at the start of every such path.
+        let mut node = self.get_root();
+        for &c in fragment.iter().rev() {
+            match node.get_child(c) {
+                Some(child) => node = child,
+                None => return Vec::new(),
+            }
+        }
+
+        let mut prefix: Vec<char> = fragment.iter().rev().copied().collect();
+        let mut words = Vec::new();
+        collect_containing(node, &mut prefix, &mut words);
+
+        // A word containing the fragment more than once is found once per occurrence
+        words.sort();
+        words.dedup();
+        words
+    }
+
+    /// The node reached by walking `word` backwards from the root.
+    pub(super) fn reversed_word_node(&self, word: &str) -> Option<&GaddagNode> {
+        let letters = normalize_letters(word)?;
+        let mut node = self.get_root();
+        for &c in letters.iter().rev() {
+            node = node.get_child(c)?;
+        }
+        Some(node)
+    }
+
+    fn words_from_rack(&self, rack: &str, use_all: bool) -> Vec<String> {
+        let mut counts = [0usize; UNIQUE_TILES];
+        for c in rack.chars() {
+            let c = c.to_ascii_uppercase();
+            if !c.is_ascii_uppercase() && c != BLANK {
+                return Vec::new();
+            }
+            counts[get_index(c)] += 1;
+        }
+
+        let Some(start) = self.get_root().get_child(PIVOT) else {
+            return Vec::new();
+        };
+
+        let mut words = Vec::new();
+        let tiles = rack.chars().count();
+        collect_from_rack(
+            start,
+            &mut counts,
+            tiles,
+            use_all,
+            &mut String::new(),
+            &mut words,
+        );
+        words
+    }
+}
+
+/// Children of `node` for the letters `A`-`Z`, in alphabetical order.
+pub(super) fn letter_children(node: &GaddagNode) -> impl Iterator<Item = (char, &GaddagNode)> {
+    INDEX_TO_CHAR
+        .iter()
+        .filter(|&&c| c != BLANK)
+        .filter_map(move |&c| node.get_child(c).map(|child| (c, child)))
+}
+
+fn normalize_letters(word: &str) -> Option<Vec<char>> {
+    let letters: Vec<char> = word.chars().map(|c| c.to_ascii_uppercase()).collect();
+    letters
+        .iter()
+        .all(char::is_ascii_uppercase)
+        .then_some(letters)
+}
+
+fn collect_from_rack(
+    node: &GaddagNode,
+    counts: &mut [usize; UNIQUE_TILES],
+    remaining: usize,
+    use_all: bool,
+    word: &mut String,
+    words: &mut Vec<String>,
+) {
+    if node.is_word() && !word.is_empty() && (!use_all || remaining == 0) {
+        words.push(word.clone());
+    }
+    if remaining == 0 {
+        return;
+    }
+
+    let blank = get_index(BLANK);
+    for (c, child) in letter_children(node) {
+        // Prefer the real tile over a blank: any word found with the blank here is also found
+        // with the tile, so trying both would only give duplicates.
+        let idx = if counts[get_index(c)] > 0 {
+            get_index(c)
+        } else if counts[blank] > 0 {
+            blank
+        } else {
+            continue;
+        };
+
+        counts[idx] -= 1;
+        word.push(c);
+        collect_from_rack(child, counts, remaining - 1, use_all, word, words);
+        word.pop();
+        counts[idx] += 1;
+    }
+}
+
+fn collect_pattern(
+    node: &GaddagNode,
+    pattern: &[char],
+    word: &mut String,
+    words: &mut Vec<String>,
+) {
+    let Some((&first, rest)) = pattern.split_first() else {
+        if node.is_word() && !word.is_empty() {
+            words.push(word.clone());
+        }
+        return;
+    };
+
+    if first == PATTERN_ANY_RUN {
+        // Match nothing, then try matching one more letter with the star still in place
+        collect_pattern(node, rest, word, words);
+        for (c, child) in letter_children(node) {
+            word.push(c);
+            collect_pattern(child, pattern, word, words);
+            word.pop();
+        }
+        return;
+    }
+
+    for (c, child) in letter_children(node) {
+        if first == PATTERN_ANY_LETTER || first == c {
+            word.push(c);
+            collect_pattern(child, rest, word, words);
+            word.pop();
+        }
+    }
+}
+
+/// Walk the remaining reversed prefix letters, then the pivot and the suffix.
+fn collect_containing(node: &GaddagNode, reversed_prefix: &mut Vec<char>, words: &mut Vec<String>) {
+    if let Some(pivot) = node.get_child(PIVOT) {
+        let mut word: String = reversed_prefix.iter().rev().collect();
+        collect_suffixes(pivot, &mut word, words);
+    }
+
+    for (c, child) in letter_children(node) {
+        reversed_prefix.push(c);
+        collect_containing(child, reversed_prefix, words);
+        reversed_prefix.pop();
+    }
+}
+
+pub(super) fn collect_suffixes(node: &GaddagNode, word: &mut String, words: &mut Vec<String>) {
+    if node.is_word() {
+        words.push(word.clone());
+    }
+    for (c, child) in letter_children(node) {
+        word.push(c);
+        collect_suffixes(child, word, words);
+        word.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaddag() -> Gaddag {
+        let words = [
+            "ACT", "CAT", "CATS", "CUT", "SCAT", "TAC", "TACT", "AT", "TA", "CAST", "ACTS",
+            "SCATT", "EAT", "TEA", "ATE", "ETA",
+        ];
+        Gaddag::from_wordlist(&words.iter().map(|w| w.to_string()).collect())
+    }
+
+    #[test]
+    fn anagrams_use_all_tiles() {
+        let g = gaddag();
+        assert_eq!(g.anagrams("TCA"), vec!["ACT", "CAT", "TAC"]);
+        assert_eq!(
+            g.anagrams("cat?"),
+            vec!["ACTS", "CAST", "CATS", "SCAT", "TACT"]
+        );
+        assert_eq!(g.anagrams("??"), vec!["AT", "TA"]);
+        assert!(g.anagrams("QQ").is_empty());
+        assert!(g.anagrams("C1T").is_empty());
+    }
+
+    #[test]
+    fn subanagrams_use_any_tiles_once() {
+        let g = gaddag();
+        assert_eq!(g.subanagrams("CAT"), vec!["ACT", "AT", "CAT", "TA", "TAC"]);
+
+        // The blank may only stand in once, so no duplicates and no words needing two blanks
+        let words = g.subanagrams("EA?");
+        assert_eq!(words, vec!["AT", "ATE", "EAT", "ETA", "TA", "TEA"]);
+
+        // Any number of copies of a letter, not just what a rack holds
+        let many = format!("{}CT", "A".repeat(300));
+        assert_eq!(g.subanagrams(&many), g.subanagrams("ACT"));
+        assert!(g.anagrams(&many).is_empty());
+    }
+
+    #[test]
+    fn pattern_wildcards() {
+        let g = gaddag();
+        assert_eq!(g.matching_pattern("C?T"), vec!["CAT", "CUT"]);
+        assert_eq!(g.matching_pattern("c?t*"), vec!["CAT", "CATS", "CUT"]);
+        assert_eq!(g.matching_pattern("*T*T*"), vec!["SCATT", "TACT"]);
+        assert_eq!(g.matching_pattern("*").len(), 16);
+        assert!(g.matching_pattern("C1T").is_empty());
+    }
+
+    #[test]
+    fn words_containing_fragment() {
+        let g = gaddag();
+        assert_eq!(
+            g.containing("CA"),
+            vec!["CAST", "CAT", "CATS", "SCAT", "SCATT"]
+        );
+        assert_eq!(g.containing("TT"), vec!["SCATT"]);
+        assert_eq!(g.containing("T").len(), 16);
+        assert!(g.containing("XYZ").is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+use std::io;
+
+use crate::constants::PIVOT;
+use crate::move_generation::gaddag::queries::letter_children;
+use crate::move_generation::gaddag::{Gaddag, GaddagNode};
+
+/// The lexicons a word of a merged Gaddag comes from: bit `i` is set for the `i`-th lexicon
+/// given to `Gaddag::merged`.
+pub type SourceFlags = u8;
+
+/// Words added and removed between two editions of a lexicon, and the words they share.
+/// Every list is in alphabetical order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LexiconDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub kept: Vec<String>,
+}
+
+impl LexiconDiff {
+    /// Write the changes in alphabetical order, one per line: `+WORD` for added words and
+    /// `-WORD` for removed ones.
+    pub fn write_changes(&self, out: &mut impl io::Write) -> io::Result<()> {
+        let mut added = self.added.iter().peekable();
+        let mut removed = self.removed.iter().peekable();
+        loop {
+            let take_added = match (added.peek(), removed.peek()) {
+                (Some(a), Some(r)) => a < r,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return Ok(()),
+            };
+            if take_added {
+                writeln!(out, "+{}", added.next().unwrap())?;
+            } else {
+                writeln!(out, "-{}", removed.next().unwrap())?;
+            }
+        }
+    }
+}
+
+// Set operations walk the forward tries below the root's pivot child of both lexicons side by
+// side, so every word is visited once and the results come out in alphabetical order.
+impl Gaddag {
+    /// Every word in the lexicon, in alphabetical order.
+    pub fn word_list(&self) -> Vec<String> {
+        self.words().collect()
+    }
+
+    /// Words in `self` or `other`.
+    pub fn union(&self, other: &Gaddag) -> Vec<String> {
+        self.select(other, |in_self, in_other| in_self || in_other)
+    }
+
+    /// Words in both `self` and `other`.
+    pub fn intersection(&self, other: &Gaddag) -> Vec<String> {
+        self.select(other, |in_self, in_other| in_self && in_other)
+    }
+
+    /// Words in `self` but not in `other`.
+    pub fn difference(&self, other: &Gaddag) -> Vec<String> {
+        self.select(other, |in_self, in_other| in_self && !in_other)
+    }
+
+    /// Words in exactly one of `self` and `other`.
+    pub fn symmetric_difference(&self, other: &Gaddag) -> Vec<String> {
+        self.select(other, |in_self, in_other| in_self != in_other)
+    }
+
+    /// Compare `self` as the old edition of a lexicon against `newer`.
+    pub fn diff(&self, newer: &Gaddag) -> LexiconDiff {
+        let mut diff = LexiconDiff::default();
+        self.walk_both(newer, |word, in_self, in_newer| {
+            let list = match (in_self, in_newer) {
+                (true, true) => &mut diff.kept,
+                (true, false) => &mut diff.removed,
+                _ => &mut diff.added,
+            };
+            list.push(word.to_string());
+        });
+        diff
+    }
+
+    /// Build a single Gaddag with the words of all `lexicons`, where each word is flagged with
+    /// the lexicons it comes from (see `sources`).
+    ///
+    /// # Panics
+    /// If there are more lexicons than bits in `SourceFlags`.
+    pub fn merged(lexicons: &[&Gaddag]) -> Gaddag {
+        assert!(
+            lexicons.len() <= SourceFlags::BITS as usize,
+            "at most {} lexicons can be merged",
+            SourceFlags::BITS
+        );
+
+        let mut merged = Gaddag {
+            root: GaddagNode::new(),
+        };
+        for (i, lexicon) in lexicons.iter().enumerate() {
+            for word in lexicon.word_list() {
+                merged.root.insert_gaddag_with_sources(&word, 1 << i);
+            }
+        }
+        merged
+    }
+
+    /// The lexicons `word` comes from in a merged Gaddag, 0 if it is not a word. In a
+    /// Gaddag built from a single word list every word has source 1.
+    pub fn sources(&self, word: &str) -> SourceFlags {
+        self.reversed_word_node(word)
+            .and_then(|node| node.get_child(PIVOT))
+            .map_or(0, GaddagNode::sources)
+    }
+
+    /// Every word with its sources, in alphabetical order.
+    pub fn words_with_sources(&self) -> Vec<(String, SourceFlags)> {
+        let mut words = Vec::new();
+        if let Some(pivot) = self.get_root().get_child(PIVOT) {
+            collect_with_sources(pivot, &mut String::new(), &mut words);
+        }
+        words
+    }
+
+    fn select(&self, other: &Gaddag, keep: impl Fn(bool, bool) -> bool) -> Vec<String> {
+        let mut words = Vec::new();
+        self.walk_both(other, |word, in_self, in_other| {
+            if keep(in_self, in_other) {
+                words.push(word.to_string());
+            }
+        });
+        words
+    }
+
+    /// Call `visit` with every word of either lexicon and whether each lexicon has it.
+    fn walk_both(&self, other: &Gaddag, mut visit: impl FnMut(&str, bool, bool)) {
+        walk_both(
+            self.get_root().get_child(PIVOT),
+            other.get_root().get_child(PIVOT),
+            &mut String::new(),
+            &mut visit,
+        );
+    }
+}
+
+fn collect_with_sources(
+    node: &GaddagNode,
+    word: &mut String,
+    words: &mut Vec<(String, SourceFlags)>,
+) {
+    if node.is_word() {
+        words.push((word.clone(), node.sources()));
+    }
+    for (c, child) in letter_children(node) {
+        word.push(c);
+        collect_with_sources(child, word, words);
+        word.pop();
+    }
+}
+
+fn walk_both(
+    a: Option<&GaddagNode>,
+    b: Option<&GaddagNode>,
+    word: &mut String,
+    visit: &mut impl FnMut(&str, bool, bool),
+) {
+    let in_a = a.is_some_and(GaddagNode::is_word);
+    let in_b = b.is_some_and(GaddagNode::is_word);
+    if in_a || in_b {
+        visit(word, in_a, in_b);
+    }
+
+    for c in 'A'..='Z' {
+        let child_a = a.and_then(|node| node.get_child(c));
+        let child_b = b.and_then(|node| node.get_child(c));
+        if child_a.is_none() && child_b.is_none() {
+            continue;
+        }
+        word.push(c);
+        walk_both(child_a, child_b, word, visit);
+        word.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaddag(words: &[&str]) -> Gaddag {
+        Gaddag::from_wordlist(&words.iter().map(|w| w.to_string()).collect())
+    }
+
+    #[test]
+    fn set_operations_are_alphabetical() {
+        let old = gaddag(&["CAT", "CATS", "DOG", "QI"]);
+        let new = gaddag(&["CAT", "DOG", "DOGS", "OK", "ZA"]);
+
+        assert_eq!(old.word_list(), vec!["CAT", "CATS", "DOG", "QI"]);
+        assert_eq!(
+            old.union(&new),
+            vec!["CAT", "CATS", "DOG", "DOGS", "OK", "QI", "ZA"]
+        );
+        assert_eq!(old.intersection(&new), vec!["CAT", "DOG"]);
+        assert_eq!(old.difference(&new), vec!["CATS", "QI"]);
+        assert_eq!(new.difference(&old), vec!["DOGS", "OK", "ZA"]);
+        assert_eq!(
+            old.symmetric_difference(&new),
+            vec!["CATS", "DOGS", "OK", "QI", "ZA"]
+        );
+        assert!(old.difference(&old).is_empty());
+    }
+
+    #[test]
+    fn diff_lists_added_and_removed_words() {
+        let old = gaddag(&["CAT", "CATS", "DOG", "QI"]);
+        let new = gaddag(&["CAT", "DOG", "DOGS", "OK", "ZA"]);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec!["DOGS", "OK", "ZA"]);
+        assert_eq!(diff.removed, vec!["CATS", "QI"]);
+        assert_eq!(diff.kept, vec!["CAT", "DOG"]);
+
+        let mut out = Vec::new();
+        diff.write_changes(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "-CATS\n+DOGS\n+OK\n-QI\n+ZA\n"
+        );
+    }
+
+    #[test]
+    fn merged_gaddag_flags_sources() {
+        let old = gaddag(&["CAT", "CATS", "DOG"]);
+        let new = gaddag(&["CAT", "DOG", "DOGS"]);
+        let merged = Gaddag::merged(&[&old, &new]);
+
+        assert_eq!(merged.sources("CAT"), 0b11);
+        assert_eq!(merged.sources("cats"), 0b01);
+        assert_eq!(merged.sources("DOGS"), 0b10);
+        assert_eq!(merged.sources("DO"), 0);
+        assert_eq!(old.sources("CAT"), 1);
+
+        // The merged lexicon is a full Gaddag: words can be found from any pivot
+        assert!(merged.is_word("CATS") && merged.is_word("DOGS"));
+        assert_eq!(merged.back_hooks("DOG"), vec!['S']);
+        assert_eq!(
+            merged.words_with_sources(),
+            vec![
+                ("CAT".to_string(), 0b11),
+                ("CATS".to_string(), 0b01),
+                ("DOG".to_string(), 0b11),
+                ("DOGS".to_string(), 0b10),
+            ]
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use crate::constants::{INDEX_TO_CHAR, PIVOT};
+use crate::move_generation::gaddag::{Gaddag, GaddagNode};
+
+/// Number of letters `A`-`Z` at the start of `INDEX_TO_CHAR`.
+const LETTERS: usize = 26;
+
+/// Lazy iterator over the words of a `Gaddag`, created by `Gaddag::words` and
+/// `Gaddag::words_by_length`.
+///
+/// Every word W is stored once as the path that starts with the pivot and then spells W
+/// forwards, so walking the pivot child of the root depth first gives every word exactly once
+/// and in alphabetical order. Length buckets walk the same trie once per length, stopping at
+/// that depth.
+pub struct Words<'a> {
+    start: Option<&'a GaddagNode>,
+    stack: Vec<Frame<'a>>,
+    word: String,
+    /// The word length of the current bucket, `None` for alphabetical order.
+    length: Option<usize>,
+    /// Whether the current bucket stopped at a node with longer words below it.
+    longer_words: bool,
+}
+
+struct Frame<'a> {
+    node: &'a GaddagNode,
+    /// Index in `INDEX_TO_CHAR` of the next child to visit.
+    next: usize,
+}
+
+impl<'a> Words<'a> {
+    fn new(start: Option<&'a GaddagNode>, length: Option<usize>) -> Self {
+        Self {
+            start,
+            stack: start
+                .map(|node| Frame { node, next: 0 })
+                .into_iter()
+                .collect(),
+            word: String::new(),
+            length,
+            longer_words: false,
+        }
+    }
+
+    /// Start the next length bucket, if any word is longer than the current one.
+    fn next_bucket(&mut self) -> bool {
+        match (self.length, self.start) {
+            (Some(length), Some(node)) if self.longer_words => {
+                self.length = Some(length + 1);
+                self.longer_words = false;
+                self.stack.push(Frame { node, next: 0 });
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Iterator for Words<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            let Some(frame) = self.stack.last_mut() else {
+                if self.next_bucket() {
+                    continue;
+                }
+                return None;
+            };
+
+            let at_bucket_length = self.length == Some(self.word.len());
+            let child = if at_bucket_length {
+                None
+            } else {
+                (frame.next..LETTERS).find_map(|i| {
+                    let c = INDEX_TO_CHAR[i];
+                    frame.node.get_child(c).map(|child| (i, c, child))
+                })
+            };
+
+            let Some((i, c, child)) = child else {
+                // Paths are only stored for words, so any child leads to a longer word
+                if at_bucket_length && frame.node.children_mask != 0 {
+                    self.longer_words = true;
+                }
+                self.stack.pop();
+                self.word.pop();
+                continue;
+            };
+
+            frame.next = i + 1;
+            self.stack.push(Frame {
+                node: child,
+                next: 0,
+            });
+            self.word.push(c);
+
+            let in_bucket = self.length.is_none_or(|length| length == self.word.len());
+            if child.is_word() && in_bucket {
+                return Some(self.word.clone());
+            }
+        }
+    }
+}
+
+/// Word count and length distribution of a lexicon.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WordStats {
+    pub count: usize,
+    /// Number of words of each length, indexed by length.
+    pub by_length: Vec<usize>,
+}
+
+impl WordStats {
+    /// Length of the shortest word, `None` for an empty lexicon.
+    pub fn min_length(&self) -> Option<usize> {
+        self.by_length.iter().position(|&n| n > 0)
+    }
+
+    /// Length of the longest word, `None` for an empty lexicon.
+    pub fn max_length(&self) -> Option<usize> {
+        self.by_length.iter().rposition(|&n| n > 0)
+    }
+
+    /// Average word length, 0 for an empty lexicon.
+    pub fn mean_length(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let letters: usize = self
+            .by_length
+            .iter()
+            .enumerate()
+            .map(|(length, &n)| length * n)
+            .sum();
+        letters as f64 / self.count as f64
+    }
+}
+
+impl Gaddag {
+    /// Every word in alphabetical order.
+    pub fn words(&self) -> Words<'_> {
+        Words::new(self.get_root().get_child(PIVOT), None)
+    }
+
+    /// Every word, shortest first and in alphabetical order within each length.
+    pub fn words_by_length(&self) -> Words<'_> {
+        Words::new(self.get_root().get_child(PIVOT), Some(1))
+    }
+
+    /// Count the words of each length without building the words themselves.
+    pub fn word_stats(&self) -> WordStats {
+        let mut stats = WordStats::default();
+        if let Some(start) = self.get_root().get_child(PIVOT) {
+            count_words(start, 0, &mut stats);
+        }
+        stats
+    }
+}
+
+fn count_words(node: &GaddagNode, depth: usize, stats: &mut WordStats) {
+    if node.is_word() {
+        if stats.by_length.len() <= depth {
+            stats.by_length.resize(depth + 1, 0);
+        }
+        stats.by_length[depth] += 1;
+        stats.count += 1;
+    }
+    for child in &node.children_ptrs {
+        count_words(child, depth + 1, stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaddag(words: &[&str]) -> Gaddag {
+        Gaddag::from_wordlist(&words.iter().map(|w| w.to_string()).collect())
+    }
+
+    #[test]
+    fn words_are_listed_once_in_alphabetical_order() {
+        let g = gaddag(&["TAB", "CAT", "AT", "CATS", "A", "ZYZZYVA", "CAT", "TA"]);
+        let words: Vec<String> = g.words().collect();
+        assert_eq!(
+            words,
+            vec!["A", "AT", "CAT", "CATS", "TA", "TAB", "ZYZZYVA"]
+        );
+
+        // The iterator is lazy and can be stopped early
+        assert_eq!(g.words().nth(2).as_deref(), Some("CAT"));
+        assert_eq!(gaddag(&[]).words().count(), 0);
+    }
+
+    #[test]
+    fn words_by_length_are_bucketed() {
+        let g = gaddag(&["TAB", "CAT", "AT", "CATS", "A", "ZYZZYVA", "TA"]);
+        let words: Vec<String> = g.words_by_length().collect();
+        assert_eq!(
+            words,
+            vec!["A", "AT", "TA", "CAT", "TAB", "CATS", "ZYZZYVA"]
+        );
+        assert_eq!(gaddag(&[]).words_by_length().next(), None);
+    }
+
+    #[test]
+    fn stats_count_words_by_length() {
+        let g = gaddag(&["TAB", "CAT", "AT", "CATS", "ZYZZYVA", "TA"]);
+        let stats = g.word_stats();
+        assert_eq!(stats.count, 6);
+        assert_eq!(stats.count, g.words().count());
+        assert_eq!(stats.by_length, vec![0, 0, 2, 2, 1, 0, 0, 1]);
+        assert_eq!(stats.min_length(), Some(2));
+        assert_eq!(stats.max_length(), Some(7));
+        assert!((stats.mean_length() - 21.0 / 6.0).abs() < 1e-9);
+
+        let empty = gaddag(&[]).word_stats();
+        assert_eq!(empty.min_length(), None);
+        assert_eq!(empty.mean_length(), 0.0);
+    }
+}
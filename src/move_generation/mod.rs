@@ -1,5 +1,9 @@
 mod gaddag;
 mod move_context;
 mod move_generator;
+mod reference;
 
+pub use gaddag::{Gaddag, GaddagNode, Hooks, LexiconDiff, SourceFlags, WordStats, Words};
+pub use move_context::GeneratorContext;
 pub use move_generator::MoveGenerator;
+pub use reference::ReferenceGenerator;
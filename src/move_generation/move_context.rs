@@ -1,14 +1,12 @@
 #![allow(dead_code)]
 
-use std::collections::HashSet;
-
 use crate::constants::{BOARD_SIZE, BoardPosition, EMPTY_TILE, PIVOT, RACK_SIZE, TOTAL_SIZE};
 use crate::core::{Board, CrosswordMove, Rack};
 use crate::move_generation::gaddag::GaddagNode;
 
 pub struct GeneratorContext {
     // Store values
-    pub moves: HashSet<CrosswordMove>,
+    pub moves: Vec<CrosswordMove>,
     pub explored_anchors: [bool; TOTAL_SIZE],
 
     // Precomputer buffers
@@ -43,7 +41,7 @@ pub enum ExtendAction {
 
 impl GeneratorContext {
     pub fn new(board: &Board) -> Self {
-        let moves = HashSet::new();
+        let moves = Vec::new();
         let explored_anchors = [false; TOTAL_SIZE];
 
         let mut hori_buffers = [[EMPTY_TILE; BOARD_SIZE]; BOARD_SIZE];
@@ -163,8 +161,13 @@ impl<'a> RecursionContext<'a> {
     }
 
     #[inline]
+    /// Tile `modifyer` squares away from the current depth; squares off the line read as empty
     pub fn current_tile_with_mod(&self, modifyer: i32) -> char {
-        self.buffer[(self.depth + modifyer) as usize]
+        usize::try_from(self.depth + modifyer)
+            .ok()
+            .and_then(|index| self.buffer.get(index))
+            .copied()
+            .unwrap_or(EMPTY_TILE)
     }
 
     pub fn extend(&mut self, action: &ExtendAction, new_node: &'a GaddagNode) {
@@ -180,7 +183,6 @@ impl<'a> RecursionContext<'a> {
             }
             ExtendAction::TraversePivot() => {
                 self.update_direction();
-                return;
             }
         }
     }
@@ -192,7 +194,6 @@ impl<'a> RecursionContext<'a> {
         match action {
             ExtendAction::TraversePivot() => {
                 self.update_direction();
-                return;
             }
             ExtendAction::PlaceFromRack(idx, _) => {
                 self.revert_move(*idx);
@@ -6,7 +6,6 @@ mod recursion;
 use crate::constants::TOTAL_SIZE;
 use crate::core::{Board, CrosswordMove, Rack};
 use crate::move_generation::{gaddag::Gaddag, move_context::GeneratorContext};
-use std::collections::HashSet;
 
 pub struct MoveGenerator<'a> {
     gaddag: &'a Gaddag,
@@ -17,8 +16,13 @@ impl<'a> MoveGenerator<'a> {
         Self { gaddag }
     }
 
+    /// Every distinct move for `rack` on `board`, each exactly once.
+    ///
+    /// Moves along a line are only generated from their first anchor: extending backwards
+    /// stops at anchors that were already explored. The only play both passes can find is a
+    /// single tile, which is kept from the pass along its main word (see `record_move`).
     // Generator context is made and owned here
-    pub fn generate_all_moves(&self, board: &Board, rack: &mut Rack) -> HashSet<CrosswordMove> {
+    pub fn generate_all_moves(&self, board: &Board, rack: &mut Rack) -> Vec<CrosswordMove> {
         // Create move context blocks
         let mut gen_ctx: GeneratorContext = GeneratorContext::new(board);
 
@@ -28,12 +32,8 @@ impl<'a> MoveGenerator<'a> {
             return gen_ctx.moves;
         }
 
-        // Start generating moves
-        for index in 0..TOTAL_SIZE {
-            if !board.is_anchor(index) {
-                continue;
-            }
-
+        // Start generating moves, anchors in board order
+        for index in board.anchors().iter() {
             // Generate moves for anchor
             self.generate_moves_for_anchor(rack, &mut gen_ctx, index);
 
@@ -51,6 +51,7 @@ mod tests {
     use crate::constants::{BOARD_SIZE, BoardPosition, EMPTY_TILE, RACK_SIZE, TOTAL_SIZE};
     use crate::core::{Board, Rack};
     use crate::move_generation::gaddag::Gaddag;
+    use std::collections::HashSet;
 
     #[test]
     fn empty_board_returns_moves() {
@@ -185,4 +186,77 @@ mod tests {
 
         assert!(found_s, "Expected 'S' to be placed at position 11");
     }
+
+    /// The word through `pos` along `step` (1 or `BOARD_SIZE`) with `tile` placed there.
+    fn word_through(board: &Board, pos: usize, tile: char, step: usize) -> String {
+        let in_line = |a: usize, b: usize| step == BOARD_SIZE || a / BOARD_SIZE == b / BOARD_SIZE;
+        let mut start = pos;
+        while start >= step && in_line(start - step, pos) && !board.is_cell_empty(start - step) {
+            start -= step;
+        }
+        let mut word = String::new();
+        let mut i = start;
+        while i < TOTAL_SIZE && in_line(i, pos) && (i == pos || !board.is_cell_empty(i)) {
+            word.push(if i == pos { tile } else { board.get(i) });
+            i += step;
+        }
+        word
+    }
+
+    #[test]
+    fn moves_are_generated_once() {
+        // One-letter words make single tiles valid in both directions
+        let words = [
+            "A", "AA", "AS", "AT", "CAT", "CATS", "SAT", "SCAT", "TA", "TAS", "TAT",
+        ];
+        let gaddag = Gaddag::from_wordlist(&words.iter().map(|w| w.to_string()).collect());
+        let generator = MoveGenerator::new(&gaddag);
+
+        let mut board = Board::new();
+        for (i, tile) in "CAT".chars().enumerate() {
+            board.place(tile, (7 * BOARD_SIZE + 6 + i) as BoardPosition);
+            board.increase_tile_count();
+        }
+        board.place('S', (8 * BOARD_SIZE + 8) as BoardPosition);
+        board.increase_tile_count();
+
+        let mut tiles = [EMPTY_TILE; RACK_SIZE];
+        for (i, tile) in "AST?".chars().enumerate() {
+            tiles[i] = tile;
+        }
+        let mut rack = Rack::from_arrays(tiles, 4);
+        let moves = generator.generate_all_moves(&board, &mut rack);
+
+        // Same moves as collecting into a set, without the duplicates
+        let unique: HashSet<&CrosswordMove> = moves.iter().collect();
+        assert_eq!(unique.len(), moves.len());
+        assert!(moves.iter().any(|m| m.len() > 1));
+
+        // Every legal single tile appears exactly once
+        let mut singles = 0;
+        for pos in (0..TOTAL_SIZE).filter(|&pos| board.is_anchor(pos)) {
+            let letters = "AST".chars().chain('a'..='z');
+            for tile in letters {
+                let formed = [
+                    word_through(&board, pos, tile, 1),
+                    word_through(&board, pos, tile, BOARD_SIZE),
+                ];
+                if formed
+                    .iter()
+                    .all(|w| w.len() == 1 || gaddag.is_word(&w.to_ascii_uppercase()))
+                {
+                    singles += 1;
+                    let found = moves
+                        .iter()
+                        .filter(|m| {
+                            m.len() == 1 && m.iter().next() == Some((tile, pos as BoardPosition))
+                        })
+                        .count();
+                    assert_eq!(found, 1, "{tile} at {pos} found {found} times");
+                }
+            }
+        }
+        assert!(singles > 0);
+        assert_eq!(moves.iter().filter(|m| m.len() == 1).count(), singles);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::constants::{BOARD_SIZE, EMPTY_TILE};
+use crate::constants::{BLANK, BOARD_SIZE, EMPTY_TILE, INDEX_TO_CHAR, TileBitboard, get_index};
 use crate::core::CrosswordMove;
 use crate::move_generation::{MoveGenerator, move_context::*};
 
@@ -60,56 +60,96 @@ impl<'a> MoveGenerator<'a> {
 
         // Build the crossword word (with the placed tile at `idx_in_line`) and use
         // the GADDAG's is_word method to check validity
-        let mut word = String::with_capacity(length);
-        for i in start..=end {
-            let tile = if i == idx_in_line {
-                placed_tile
-            } else {
-                crossline[i]
-            };
-            word.push(tile);
-        }
-
-        // DEBUG: show what word we're checking in tests
-        println!(
-            "[DEBUG is_crossword_valid] board_pos={}, is_horizontal={}, line_idx={}, start={}, end={}, word={}",
-            board_pos, is_horizontal, line_idx, start, end, word
-        );
+        let word: String = crossline[start..=end]
+            .iter()
+            .enumerate()
+            .map(|(i, &tile)| {
+                if start + i == idx_in_line {
+                    placed_tile
+                } else {
+                    tile
+                }
+            })
+            .collect();
 
         self.gaddag.is_word(&word)
     }
 
     pub fn record_move(&self, gen_ctx: &mut GeneratorContext, rec_ctx: &RecursionContext) {
+        // A single tile is found by the horizontal and the vertical pass when it forms words
+        // both ways. Keep it from one pass only, using the same rule as scoring: the play is
+        // horizontal when the tile touches a tile to its left or right.
+        if rec_ctx.current_move_len == 1 {
+            let pos = rec_ctx.current_positions[0] as usize;
+            if rec_ctx.is_horizontal != self.has_horizontal_neighbour(gen_ctx, pos) {
+                return;
+            }
+        }
+
         // Once again, just copying
         let crossword_move = CrosswordMove::from_arrays(
             rec_ctx.current_tiles,
             rec_ctx.current_positions,
             rec_ctx.current_move_len,
         );
-        gen_ctx.moves.insert(crossword_move);
+        gen_ctx.moves.push(crossword_move);
+    }
+
+    fn has_horizontal_neighbour(&self, gen_ctx: &GeneratorContext, board_pos: usize) -> bool {
+        let row = &gen_ctx.hori_buffers[board_pos / BOARD_SIZE];
+        let col = board_pos % BOARD_SIZE;
+        (col > 0 && row[col - 1] != EMPTY_TILE)
+            || (col + 1 < BOARD_SIZE && row[col + 1] != EMPTY_TILE)
     }
 
     pub fn handle_empty_tile(&self, gen_ctx: &mut GeneratorContext, ctx: &'a mut RecursionContext) {
         let tiles: Vec<_> = ctx.rack.available_tiles().collect(); // Cannot iterate over while changing
+        let mut tried: TileBitboard = 0;
         for (idx, tile) in tiles {
-            if !self.is_crossword_valid(gen_ctx, tile, ctx.position_at_depth(), ctx.is_horizontal) {
+            // Duplicate tiles on the rack would only produce the same moves again
+            let bit: TileBitboard = 1 << get_index(tile);
+            if tried & bit != 0 {
                 continue;
             }
+            tried |= bit;
 
-            if let Some(next_node) = ctx.node.get_child(tile) {
-                let previous_node = ctx.node;
-                let action = ExtendAction::PlaceFromRack(idx, tile);
-                ctx.extend(&action, next_node);
-                if ctx.is_forwards {
-                    self.extend_forwards(gen_ctx, ctx);
-                } else {
-                    self.extend_backwards(gen_ctx, ctx);
+            if tile == BLANK {
+                // A blank can stand for any letter; it is stored as the lowercase letter
+                for letter in INDEX_TO_CHAR.iter().filter(|&&c| c != BLANK) {
+                    self.try_place_tile(gen_ctx, ctx, idx, letter.to_ascii_lowercase());
                 }
-                ctx.undo(&action, previous_node);
+            } else {
+                self.try_place_tile(gen_ctx, ctx, idx, tile);
             }
         }
     }
 
+    fn try_place_tile(
+        &self,
+        gen_ctx: &mut GeneratorContext,
+        ctx: &mut RecursionContext<'_>,
+        rack_idx: usize,
+        tile: char,
+    ) {
+        let Some(next_node) = ctx.node.get_child(tile) else {
+            return;
+        };
+
+        if !self.is_crossword_valid(gen_ctx, tile, ctx.position_at_depth(), ctx.is_horizontal) {
+            return;
+        }
+
+        let previous_node = ctx.node;
+        let action = ExtendAction::PlaceFromRack(rack_idx, tile);
+        ctx.extend(&action, next_node);
+        if ctx.is_forwards {
+            self.extend_forwards(gen_ctx, ctx);
+        } else {
+            self.extend_backwards(gen_ctx, ctx);
+        }
+        ctx.undo(&action, previous_node);
+    }
+
     pub fn follow_existing_tiles(
         &self,
         gen_ctx: &mut GeneratorContext,
@@ -122,7 +162,7 @@ impl<'a> MoveGenerator<'a> {
             let action: ExtendAction = ExtendAction::TraverseExisting();
             ctx.extend(&action, next_node);
             if ctx.is_forwards {
-                    self.extend_forwards(gen_ctx, ctx);
+                self.extend_forwards(gen_ctx, ctx);
             } else {
                 self.extend_backwards(gen_ctx, ctx);
             }
@@ -146,7 +186,7 @@ mod tests {
     }
 
     fn default_setup() -> Context {
-        return setup(Vec::new(), "", "");
+        setup(Vec::new(), "", "")
     }
 
     fn setup(valid_words: Vec<&str>, rack_letters: &str, placed_word: &str) -> Context {
@@ -170,10 +210,10 @@ mod tests {
         let gen_ctx = GeneratorContext::new(&board);
 
         Context {
-            gaddag: gaddag,
-            board: board,
-            rack: rack,
-            gen_ctx: gen_ctx,
+            gaddag,
+            board,
+            rack,
+            gen_ctx,
         }
     }
 
@@ -183,14 +223,14 @@ mod tests {
         let generator = MoveGenerator::new(&setup.gaddag);
 
         let crossline_1 = generator.get_cross_line(&setup.gen_ctx, 1, false);
-        for tile in crossline_1.into_iter() {
+        for tile in crossline_1.iter() {
             assert!(*tile == EMPTY_TILE);
         }
 
         let crossline_2 = generator.get_cross_line(&setup.gen_ctx, 7, false);
         assert!(
             crossline_2
-                .into_iter()
+                .iter()
                 .filter(|&tile| *tile != EMPTY_TILE)
                 .count()
                 == 3
@@ -204,9 +244,9 @@ mod tests {
 
         let start = TOTAL_SIZE / 2;
         // Place 'S' just after the existing placed word horizontally (row)
-        let pos_after = (start + 3) as usize; // one past the placed "CAT"
+        let pos_after = start + 3; // one past the placed "CAT"
         // Check placing 'S' vertically at the middle of the placed word's column
-        let pos_middle = (start + 1) as usize;
+        let pos_middle = start + 1;
 
         assert!(generator.is_crossword_valid(&setup.gen_ctx, 'S', pos_after, false));
         assert!(generator.is_crossword_valid(&setup.gen_ctx, 'S', pos_middle, true));
@@ -0,0 +1,386 @@
+use std::collections::BTreeSet;
+
+use crate::constants::{BLANK, BOARD_SIZE, BoardPosition, EMPTY_TILE, RACK_SIZE, TOTAL_SIZE};
+use crate::core::{Board, CrosswordMove, Rack};
+use crate::move_generation::gaddag::Gaddag;
+
+/// A deliberately simple and slow move generator to test `MoveGenerator` against.
+///
+/// It tries every arrangement of the rack tiles on the next empty squares from every square
+/// of every row and column, and keeps the placements whose words all pass
+/// `Gaddag::is_word`. It only uses the GADDAG as a word list.
+///
+/// Moves come out sorted, each with its tiles in board order. Compare them with the output
+/// of `MoveGenerator` through `ReferenceGenerator::canonical`, which does not depend on tile order.
+pub struct ReferenceGenerator<'a> {
+    gaddag: &'a Gaddag,
+}
+
+impl<'a> ReferenceGenerator<'a> {
+    pub fn new(gaddag: &'a Gaddag) -> Self {
+        Self { gaddag }
+    }
+
+    pub fn generate_all_moves(&self, board: &Board, rack: &Rack) -> Vec<CrosswordMove> {
+        let tiles: Vec<char> = rack.available_tiles().map(|(_, tile)| tile).collect();
+        let mut arrangements = BTreeSet::new();
+        arrange(
+            &tiles,
+            &mut vec![false; tiles.len()],
+            &mut Vec::new(),
+            &mut arrangements,
+        );
+
+        let mut found = BTreeSet::new();
+        for is_horizontal in [true, false] {
+            for line in 0..BOARD_SIZE {
+                for start in 0..BOARD_SIZE {
+                    let squares: Vec<usize> = (start..BOARD_SIZE)
+                        .map(|i| square(line, i, is_horizontal))
+                        .filter(|&pos| board.is_cell_empty(pos))
+                        .take(tiles.len())
+                        .collect();
+                    if squares.first() != Some(&square(line, start, is_horizontal)) {
+                        continue;
+                    }
+
+                    for arrangement in &arrangements {
+                        if arrangement.len() > squares.len() {
+                            continue;
+                        }
+                        let placement: Vec<(usize, char)> = squares
+                            .iter()
+                            .copied()
+                            .zip(arrangement.iter().copied())
+                            .collect();
+                        if self.is_legal(board, &placement, is_horizontal) {
+                            found.insert(placement);
+                        }
+                    }
+                }
+            }
+        }
+
+        found
+            .into_iter()
+            .map(|placement| to_move(&placement))
+            .collect()
+    }
+
+    /// The tiles of a move as (square, tile) pairs in board order, to compare moves found in
+    /// different orders.
+    pub fn canonical(crossword_move: &CrosswordMove) -> Vec<(BoardPosition, char)> {
+        let mut tiles: Vec<(BoardPosition, char)> = crossword_move
+            .iter()
+            .map(|(tile, pos)| (pos, tile))
+            .collect();
+        tiles.sort();
+        tiles
+    }
+
+    /// Whether `crossword_move` is a legal play on `board`: its tiles are letters on distinct
+    /// empty squares of one line, with no gaps except for tiles already on the board, and it
+    /// connects and forms only words, as for the moves of `generate_all_moves`.
+    pub fn is_valid_move(&self, board: &Board, crossword_move: &CrosswordMove) -> bool {
+        let mut placement: Vec<(usize, char)> = crossword_move
+            .iter()
+            .map(|(tile, pos)| (pos as usize, tile))
+            .collect();
+        placement.sort();
+        placement.dedup_by_key(|&mut (pos, _)| pos);
+
+        if placement.is_empty() || placement.len() != crossword_move.len() {
+            return false;
+        }
+        if placement.iter().any(|&(pos, tile)| {
+            pos >= TOTAL_SIZE || !tile.is_ascii_alphabetic() || !board.is_cell_empty(pos)
+        }) {
+            return false;
+        }
+
+        let (first, _) = placement[0];
+        let (last, _) = placement[placement.len() - 1];
+        let is_horizontal = first / BOARD_SIZE == last / BOARD_SIZE;
+        let step = if is_horizontal { 1 } else { BOARD_SIZE };
+        if !is_horizontal && first % BOARD_SIZE != last % BOARD_SIZE {
+            return false;
+        }
+        if placement.iter().any(|&(pos, _)| (pos - first) % step != 0) {
+            return false;
+        }
+        let gap = (first..=last).step_by(step).any(|pos| {
+            board.is_cell_empty(pos) && placement.binary_search_by_key(&pos, |&(p, _)| p).is_err()
+        });
+        if gap {
+            return false;
+        }
+
+        self.is_legal(board, &placement, is_horizontal)
+    }
+
+    fn is_legal(&self, board: &Board, placement: &[(usize, char)], is_horizontal: bool) -> bool {
+        let mut after = board.clone();
+        for &(pos, tile) in placement {
+            after.place(tile, pos as BoardPosition);
+        }
+
+        let connected = if board.is_empty() {
+            placement.iter().any(|&(pos, _)| pos == TOTAL_SIZE / 2)
+        } else {
+            placement
+                .iter()
+                .any(|&(pos, _)| neighbours(pos).any(|n| !board.is_cell_empty(n)))
+        };
+        if !connected {
+            return false;
+        }
+
+        let (first, _) = placement[0];
+        let mut words = vec![word_through(&after, first, is_horizontal)];
+        for &(pos, _) in placement {
+            words.push(word_through(&after, pos, !is_horizontal));
+        }
+
+        let mut formed = words.iter().filter(|word| word.len() > 1).peekable();
+        if formed.peek().is_none() {
+            // A lone tile on the empty board has to be a word on its own
+            return self.gaddag.is_word(&words[0].to_ascii_uppercase());
+        }
+        formed.all(|word| self.gaddag.is_word(&word.to_ascii_uppercase()))
+    }
+}
+
+/// Every ordered selection of one or more `tiles`, with blanks as each lowercase letter.
+fn arrange(
+    tiles: &[char],
+    used: &mut Vec<bool>,
+    current: &mut Vec<char>,
+    arrangements: &mut BTreeSet<Vec<char>>,
+) {
+    if !current.is_empty() {
+        arrangements.insert(current.clone());
+    }
+    for i in 0..tiles.len() {
+        if used[i] {
+            continue;
+        }
+        let letters: Vec<char> = if tiles[i] == BLANK {
+            ('a'..='z').collect()
+        } else {
+            vec![tiles[i]]
+        };
+
+        used[i] = true;
+        for letter in letters {
+            current.push(letter);
+            arrange(tiles, used, current, arrangements);
+            current.pop();
+        }
+        used[i] = false;
+    }
+}
+
+fn square(line: usize, i: usize, is_horizontal: bool) -> usize {
+    if is_horizontal {
+        line * BOARD_SIZE + i
+    } else {
+        i * BOARD_SIZE + line
+    }
+}
+
+fn neighbours(pos: usize) -> impl Iterator<Item = usize> {
+    let (row, col) = (pos / BOARD_SIZE, pos % BOARD_SIZE);
+    [
+        (row > 0).then(|| pos - BOARD_SIZE),
+        (row + 1 < BOARD_SIZE).then(|| pos + BOARD_SIZE),
+        (col > 0).then(|| pos - 1),
+        (col + 1 < BOARD_SIZE).then(|| pos + 1),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// The tiles of the line through `pos` that are connected to it.
+fn word_through(board: &Board, pos: usize, is_horizontal: bool) -> String {
+    let (line, at) = if is_horizontal {
+        (pos / BOARD_SIZE, pos % BOARD_SIZE)
+    } else {
+        (pos % BOARD_SIZE, pos / BOARD_SIZE)
+    };
+    let occupied = |i: usize| !board.is_cell_empty(square(line, i, is_horizontal));
+
+    let mut start = at;
+    while start > 0 && occupied(start - 1) {
+        start -= 1;
+    }
+    let mut end = at;
+    while end + 1 < BOARD_SIZE && occupied(end + 1) {
+        end += 1;
+    }
+    (start..=end)
+        .map(|i| board.get(square(line, i, is_horizontal)))
+        .collect()
+}
+
+fn to_move(placement: &[(usize, char)]) -> CrosswordMove {
+    let mut tiles = [EMPTY_TILE; RACK_SIZE];
+    let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+    for (i, &(pos, tile)) in placement.iter().enumerate() {
+        tiles[i] = tile;
+        positions[i] = pos as BoardPosition;
+    }
+    CrosswordMove::from_arrays(tiles, positions, placement.len() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::move_generation::MoveGenerator;
+
+    const WORDS: [&str; 40] = [
+        "A", "AE", "AN", "AR", "AS", "AT", "EN", "ER", "ES", "ET", "NA", "NE", "RE", "SET", "TA",
+        "TE", "ANE", "ANT", "ARE", "ART", "ATE", "EAR", "EAT", "ERA", "ERN", "ETA", "NET", "RAN",
+        "RAT", "SEA", "SAT", "TAN", "TAR", "TEA", "TEN", "NEAT", "RANT", "STAR", "TEAR", "TERN",
+    ];
+
+    fn gaddag() -> Gaddag {
+        Gaddag::from_wordlist(&WORDS.iter().map(|w| w.to_string()).collect())
+    }
+
+    fn canonical_moves(moves: &[CrosswordMove]) -> BTreeSet<Vec<(BoardPosition, char)>> {
+        moves.iter().map(ReferenceGenerator::canonical).collect()
+    }
+
+    fn compare(board: &Board, rack: &str) {
+        let gaddag = gaddag();
+        let mut rack: Rack = rack.parse().unwrap();
+        let reference = ReferenceGenerator::new(&gaddag);
+        let expected = reference.generate_all_moves(board, &rack);
+        let actual = MoveGenerator::new(&gaddag).generate_all_moves(board, &mut rack);
+
+        assert!(actual.iter().all(|m| reference.is_valid_move(board, m)));
+        assert_eq!(
+            actual.len(),
+            canonical_moves(&actual).len(),
+            "duplicate moves"
+        );
+        assert_eq!(canonical_moves(&actual), canonical_moves(&expected));
+    }
+
+    #[test]
+    fn reference_finds_plays_on_the_empty_board() {
+        let gaddag = gaddag();
+        let rack: Rack = "TAN".parse().unwrap();
+        let moves = ReferenceGenerator::new(&gaddag).generate_all_moves(&Board::new(), &rack);
+
+        let center = (TOTAL_SIZE / 2) as BoardPosition;
+        assert!(moves.iter().all(|m| m.iter().any(|(_, pos)| pos == center)));
+        // A, AN, AT, NA, TA, ANT and TAN, horizontally or vertically, in every position
+        // covering the center; A on its own only once
+        let words = moves.len();
+        assert_eq!(words, 1 + 2 * (2 * 4 + 2 * 3));
+        compare(&Board::new(), "TAN");
+    }
+
+    #[test]
+    fn generated_moves_are_valid() {
+        let gaddag = gaddag();
+        let reference = ReferenceGenerator::new(&gaddag);
+        let mut board = Board::new();
+        board.make_move(&CrosswordMove::from_notation("8G TEA", &board).unwrap());
+
+        let rack: Rack = "NRST".parse().unwrap();
+        let moves = reference.generate_all_moves(&board, &rack);
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|m| reference.is_valid_move(&board, m)));
+
+        for invalid in [
+            // Gap between the tiles
+            to_move(&[(100, 'A'), (102, 'T')]),
+            // Not on one line
+            to_move(&[(96, 'A'), (110, 'N')]),
+            // Covers a tile on the board
+            to_move(&[(111, 'S')]),
+            // Not connected
+            to_move(&[(0, 'A'), (1, 'T')]),
+            // Not a word
+            to_move(&[(125, 'N'), (126, 'N')]),
+        ] {
+            assert!(!reference.is_valid_move(&board, &invalid), "{invalid:?}");
+        }
+    }
+
+    /// Tiles scattered over the board or packed into a corner or the middle, where they make
+    /// lots of (mostly invalid) words next to each other.
+    fn scattered_board() -> impl Strategy<Value = Board> {
+        let tile = prop::sample::select(vec!['A', 'E', 'N', 'R', 'S', 'T', 'a', 'e']);
+        let region = prop::sample::select(vec![(0, BOARD_SIZE), (0, 5), (5, 10), (10, BOARD_SIZE)]);
+        (
+            region,
+            prop::collection::vec((0..BOARD_SIZE, 0..BOARD_SIZE, tile), 0..14),
+        )
+            .prop_map(|((low, high), tiles)| {
+                let mut board = Board::new();
+                for (row, col, tile) in tiles {
+                    let pos = (low + row % (high - low)) * BOARD_SIZE + low + col % (high - low);
+                    if board.is_cell_empty(pos) {
+                        board.place(tile, pos as BoardPosition);
+                        board.increase_tile_count();
+                    }
+                }
+                board
+            })
+    }
+
+    /// A board after a few turns of legal plays.
+    fn played_board() -> impl Strategy<Value = Board> {
+        prop::collection::vec((rack_strategy(), any::<prop::sample::Index>()), 1..5).prop_map(
+            |turns| {
+                let gaddag = gaddag();
+                let mut board = Board::new();
+                for (rack, choice) in turns {
+                    let rack: Rack = rack.parse().unwrap();
+                    let moves = ReferenceGenerator::new(&gaddag).generate_all_moves(&board, &rack);
+                    if !moves.is_empty() {
+                        board.make_move(&moves[choice.index(moves.len())]);
+                    }
+                }
+                board
+            },
+        )
+    }
+
+    fn rack_strategy() -> impl Strategy<Value = String> {
+        let tile = prop::sample::select(vec!['A', 'E', 'N', 'R', 'S', 'T']);
+        (prop::collection::vec(tile, 1..5), prop::bool::weighted(0.2)).prop_map(|(tiles, blank)| {
+            let mut rack: String = tiles.into_iter().collect();
+            if blank {
+                rack.pop();
+                rack.push(BLANK);
+            }
+            rack
+        })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn generator_matches_reference_on_scattered_tiles(
+            board in scattered_board(),
+            rack in rack_strategy(),
+        ) {
+            compare(&board, &rack);
+        }
+
+        #[test]
+        fn generator_matches_reference_after_legal_plays(
+            board in played_board(),
+            rack in rack_strategy(),
+        ) {
+            compare(&board, &rack);
+        }
+    }
+}
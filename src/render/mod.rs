@@ -0,0 +1,7 @@
+mod json;
+mod svg;
+mod terminal;
+
+pub use json::escape_json;
+pub use svg::{SvgRenderer, SvgTheme};
+pub use terminal::TerminalRenderer;
@@ -0,0 +1,375 @@
+use std::fmt::Write;
+
+use crate::constants::{
+    BOARD_SIZE, DOUBLE_LETTER, DOUBLE_WORD, QUADRUPLE_LETTER, QUADRUPLE_WORD, TILE_BONUSES,
+    TOTAL_SIZE, TRIPLE_LETTER, TRIPLE_WORD, tile_score,
+};
+use crate::core::{CrosswordMove, Position};
+
+/// Colours and font used by `SvgRenderer`. Colours are any SVG colour value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgTheme {
+    pub background: &'static str,
+    pub square: &'static str,
+    pub grid: &'static str,
+    pub triple_word: &'static str,
+    pub double_word: &'static str,
+    pub triple_letter: &'static str,
+    pub double_letter: &'static str,
+    pub quadruple: &'static str,
+    pub premium_text: &'static str,
+    pub tile: &'static str,
+    pub last_move_tile: &'static str,
+    pub tile_text: &'static str,
+    pub blank_text: &'static str,
+    pub label_text: &'static str,
+    pub font_family: &'static str,
+}
+
+impl SvgTheme {
+    pub fn classic() -> Self {
+        Self {
+            background: "#f4f1e8",
+            square: "#cdc7b0",
+            grid: "#f4f1e8",
+            triple_word: "#d9534f",
+            double_word: "#f0a3a3",
+            triple_letter: "#3b7dd8",
+            double_letter: "#a8d4f0",
+            quadruple: "#7a4fa0",
+            premium_text: "#ffffff",
+            tile: "#f2d98d",
+            last_move_tile: "#b5e08c",
+            tile_text: "#222222",
+            blank_text: "#c0392b",
+            label_text: "#555555",
+            font_family: "sans-serif",
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            background: "#1e1f24",
+            square: "#33353d",
+            grid: "#1e1f24",
+            triple_word: "#a33b3b",
+            double_word: "#8a4f6a",
+            triple_letter: "#2f5c9e",
+            double_letter: "#3d7287",
+            quadruple: "#5d3b7d",
+            premium_text: "#e6e6e6",
+            tile: "#d8c27a",
+            last_move_tile: "#8fc46a",
+            tile_text: "#15161a",
+            blank_text: "#a0261a",
+            label_text: "#a0a3ad",
+            font_family: "sans-serif",
+        }
+    }
+}
+
+impl Default for SvgTheme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+/// Exports a position as a self-contained SVG image: premium squares, tiles with their
+/// values, blanks, the last move, and each player's score and rack.
+///
+/// The output only depends on the input, so it can be compared against golden files.
+#[derive(Debug, Clone, Default)]
+pub struct SvgRenderer {
+    pub theme: SvgTheme,
+}
+
+/// Side of a board square in SVG units.
+const SQUARE: usize = 40;
+/// Space for the coordinate labels around the board.
+const MARGIN: usize = 30;
+/// Height of one player line below the board.
+const PLAYER_ROW: usize = 50;
+
+impl SvgRenderer {
+    pub fn new(theme: SvgTheme) -> Self {
+        Self { theme }
+    }
+
+    /// Render `position`, highlighting the tiles of `last_move` (already on the board).
+    pub fn render(&self, position: &Position, last_move: Option<&CrosswordMove>) -> String {
+        let theme = &self.theme;
+        let board_px = SQUARE * BOARD_SIZE;
+        let width = board_px + 2 * MARGIN;
+        let height = board_px + 2 * MARGIN + PLAYER_ROW * position.racks.len();
+
+        let mut highlighted = [false; TOTAL_SIZE];
+        if let Some(last_move) = last_move {
+            for (_, pos) in last_move.iter() {
+                highlighted[pos as usize] = true;
+            }
+        }
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"{}\">",
+            theme.font_family
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>",
+            theme.background
+        )
+        .unwrap();
+
+        self.write_labels(&mut svg);
+
+        for (pos, &is_highlighted) in highlighted.iter().enumerate() {
+            let x = MARGIN + (pos % BOARD_SIZE) * SQUARE;
+            let y = MARGIN + (pos / BOARD_SIZE) * SQUARE;
+            if position.board.is_cell_empty(pos) {
+                self.write_square(&mut svg, pos, x, y);
+            } else {
+                self.write_tile(
+                    &mut svg,
+                    position.board.get(pos),
+                    x,
+                    y,
+                    SQUARE,
+                    is_highlighted,
+                );
+            }
+        }
+
+        self.write_players(&mut svg, position, MARGIN * 2 + board_px);
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn write_labels(&self, svg: &mut String) {
+        let color = self.theme.label_text;
+        for i in 0..BOARD_SIZE {
+            let center = MARGIN + i * SQUARE + SQUARE / 2;
+            writeln!(
+                svg,
+                "<text x=\"{center}\" y=\"{}\" font-size=\"14\" text-anchor=\"middle\" \
+                 fill=\"{color}\">{}</text>",
+                MARGIN - 10,
+                (b'A' + i as u8) as char
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" font-size=\"14\" text-anchor=\"middle\" \
+                 fill=\"{color}\">{}</text>",
+                MARGIN / 2,
+                center + 5,
+                i + 1
+            )
+            .unwrap();
+        }
+    }
+
+    fn write_square(&self, svg: &mut String, pos: usize, x: usize, y: usize) {
+        let theme = &self.theme;
+        let (fill, label) = match TILE_BONUSES[pos] {
+            TRIPLE_WORD => (theme.triple_word, "TW"),
+            DOUBLE_WORD if pos == TOTAL_SIZE / 2 => (theme.double_word, "\u{2605}"),
+            DOUBLE_WORD => (theme.double_word, "DW"),
+            TRIPLE_LETTER => (theme.triple_letter, "TL"),
+            DOUBLE_LETTER => (theme.double_letter, "DL"),
+            QUADRUPLE_WORD => (theme.quadruple, "QW"),
+            QUADRUPLE_LETTER => (theme.quadruple, "QL"),
+            _ => (theme.square, ""),
+        };
+
+        writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{y}\" width=\"{SQUARE}\" height=\"{SQUARE}\" fill=\"{fill}\" \
+             stroke=\"{}\" stroke-width=\"2\"/>",
+            theme.grid
+        )
+        .unwrap();
+        if !label.is_empty() {
+            writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" font-size=\"13\" text-anchor=\"middle\" \
+                 fill=\"{}\">{label}</text>",
+                x + SQUARE / 2,
+                y + SQUARE / 2 + 5,
+                theme.premium_text
+            )
+            .unwrap();
+        }
+    }
+
+    /// Draw a tile of side `size` at (`x`, `y`). Blanks show their letter in the blank colour
+    /// with a ring instead of a value.
+    fn write_tile(
+        &self,
+        svg: &mut String,
+        tile: char,
+        x: usize,
+        y: usize,
+        size: usize,
+        highlighted: bool,
+    ) {
+        let theme = &self.theme;
+        let fill = if highlighted {
+            theme.last_move_tile
+        } else {
+            theme.tile
+        };
+        let is_blank = tile.is_ascii_lowercase() || tile == '?';
+        let text_color = if is_blank {
+            theme.blank_text
+        } else {
+            theme.tile_text
+        };
+
+        writeln!(
+            svg,
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"4\" fill=\"{fill}\"/>",
+            x + 1,
+            y + 1,
+            size - 2,
+            size - 2
+        )
+        .unwrap();
+        if is_blank {
+            writeln!(
+                svg,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" stroke=\"{text_color}\" \
+                 stroke-width=\"1\"/>",
+                x + size / 2,
+                y + size / 2,
+                size * 3 / 8
+            )
+            .unwrap();
+        }
+        writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-weight=\"bold\" \
+             text-anchor=\"middle\" fill=\"{text_color}\">{}</text>",
+            x + size / 2,
+            y + size * 7 / 10,
+            size / 2,
+            tile.to_ascii_uppercase()
+        )
+        .unwrap();
+        if !is_blank {
+            writeln!(
+                svg,
+                "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"end\" \
+                 fill=\"{text_color}\">{}</text>",
+                x + size - 4,
+                y + size - 4,
+                size / 4,
+                tile_score(tile)
+            )
+            .unwrap();
+        }
+    }
+
+    fn write_players(&self, svg: &mut String, position: &Position, top: usize) {
+        let theme = &self.theme;
+        for (player, rack) in position.racks.iter().enumerate() {
+            let y = top + player * PLAYER_ROW;
+            let marker = if position.to_move == Some(player) {
+                "\u{25b6} "
+            } else {
+                ""
+            };
+            let score = position.scores[player]
+                .map(|score| score.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            writeln!(
+                svg,
+                "<text x=\"{MARGIN}\" y=\"{}\" font-size=\"18\" fill=\"{}\">{marker}Player {}: \
+                 {score}</text>",
+                y + 26,
+                theme.label_text,
+                player + 1
+            )
+            .unwrap();
+
+            let rack_x = MARGIN + 8 * SQUARE;
+            for (i, tile) in rack.as_deref().unwrap_or("").chars().enumerate() {
+                self.write_tile(svg, tile, rack_x + i * SQUARE, y, SQUARE, false);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{BoardPosition, EMPTY_TILE, RACK_SIZE};
+
+    fn sample_position() -> (Position, CrosswordMove) {
+        let mut position = Position::default();
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        positions[..3].copy_from_slice(&[111, 112, 113]);
+        let cat = CrosswordMove::from_arrays(
+            [
+                'C', 'a', 'T', EMPTY_TILE, EMPTY_TILE, EMPTY_TILE, EMPTY_TILE,
+            ],
+            positions,
+            3,
+        );
+        position.board.make_move(&cat);
+        position.racks = [Some("AEIRST?".into()), Some("QU".into())];
+        position.scores = [Some(8), Some(0)];
+        position.to_move = Some(1);
+        (position, cat)
+    }
+
+    #[test]
+    fn matches_golden_file() {
+        let (position, cat) = sample_position();
+        let svg = SvgRenderer::default().render(&position, Some(&cat));
+
+        let golden = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/render/testdata/position.svg"
+        );
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(golden, &svg).unwrap();
+        }
+        assert_eq!(svg, std::fs::read_to_string(golden).unwrap());
+    }
+
+    #[test]
+    fn rendering_is_deterministic_and_themed() {
+        let (position, cat) = sample_position();
+        let classic = SvgRenderer::default().render(&position, Some(&cat));
+        assert_eq!(
+            classic,
+            SvgRenderer::default().render(&position, Some(&cat))
+        );
+
+        let dark = SvgRenderer::new(SvgTheme::dark()).render(&position, Some(&cat));
+        assert_ne!(classic, dark);
+        assert!(dark.contains(SvgTheme::dark().last_move_tile));
+        assert!(classic.contains(SvgTheme::classic().last_move_tile));
+
+        let unhighlighted = SvgRenderer::default().render(&position, None);
+        assert!(!unhighlighted.contains(SvgTheme::classic().last_move_tile));
+    }
+
+    #[test]
+    fn tiles_show_values_and_blanks_do_not() {
+        let (position, _) = sample_position();
+        let svg = SvgRenderer::default().render(&position, None);
+
+        // C is worth 3 and the blank 'a' is drawn with a ring
+        assert!(svg.contains(">C</text>"));
+        assert!(svg.contains(">3</text>"));
+        assert!(svg.contains("<circle"));
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
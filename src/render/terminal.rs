@@ -0,0 +1,169 @@
+use crate::constants::{
+    BOARD_SIZE, DOUBLE_LETTER, DOUBLE_WORD, QUADRUPLE_LETTER, QUADRUPLE_WORD, TILE_BONUSES,
+    TOTAL_SIZE, TRIPLE_LETTER, TRIPLE_WORD,
+};
+use crate::core::{Board, CrosswordMove};
+
+const RESET: &str = "\x1b[0m";
+const TILE: &str = "\x1b[1;30;43m";
+const BLANK_TILE: &str = "\x1b[1;31;43m";
+const LAST_MOVE_TILE: &str = "\x1b[1;30;42m";
+const LAST_MOVE_BLANK: &str = "\x1b[1;31;42m";
+const TRIPLE_WORD_SQUARE: &str = "\x1b[97;41m";
+const DOUBLE_WORD_SQUARE: &str = "\x1b[97;45m";
+const TRIPLE_LETTER_SQUARE: &str = "\x1b[97;44m";
+const DOUBLE_LETTER_SQUARE: &str = "\x1b[30;46m";
+const QUADRUPLE_SQUARE: &str = "\x1b[97;40m";
+const NORMAL_SQUARE: &str = "\x1b[37;100m";
+
+/// Draws a board for the terminal with row numbers, column letters and premium squares.
+///
+/// Without colour the output is plain ASCII, suitable for logs: premium squares are written
+/// as `TW`, `DW`, `TL` and `DL`, blanks as lowercase letters and the tiles of the last move
+/// in brackets. With colour, premium squares, blanks and the last move get ANSI colours.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TerminalRenderer {
+    pub color: bool,
+}
+
+impl TerminalRenderer {
+    pub fn plain() -> Self {
+        Self { color: false }
+    }
+
+    pub fn colored() -> Self {
+        Self { color: true }
+    }
+
+    /// Render `board`, highlighting the tiles placed by `last_move` if given. The move is
+    /// expected to already be on the board.
+    pub fn render(&self, board: &Board, last_move: Option<&CrosswordMove>) -> String {
+        let mut highlighted = [false; TOTAL_SIZE];
+        if let Some(last_move) = last_move {
+            for (_, pos) in last_move.iter() {
+                highlighted[pos as usize] = true;
+            }
+        }
+
+        let mut out = String::from("   ");
+        for col in 0..BOARD_SIZE {
+            out.push_str(&format!(" {} ", (b'A' + col as u8) as char));
+        }
+        out.push('\n');
+
+        for row in 0..BOARD_SIZE {
+            out.push_str(&format!("{:>2} ", row + 1));
+            for col in 0..BOARD_SIZE {
+                let pos = row * BOARD_SIZE + col;
+                if board.is_cell_empty(pos) {
+                    self.push_square(&mut out, pos);
+                } else {
+                    self.push_tile(&mut out, board.get(pos), highlighted[pos]);
+                }
+            }
+            out.push_str(&format!(" {}\n", row + 1));
+        }
+
+        out
+    }
+
+    fn push_tile(&self, out: &mut String, tile: char, highlighted: bool) {
+        let is_blank = tile.is_ascii_lowercase();
+        if self.color {
+            let style = match (highlighted, is_blank) {
+                (false, false) => TILE,
+                (false, true) => BLANK_TILE,
+                (true, false) => LAST_MOVE_TILE,
+                (true, true) => LAST_MOVE_BLANK,
+            };
+            out.push_str(&format!("{style} {tile} {RESET}"));
+        } else if highlighted {
+            out.push_str(&format!("[{tile}]"));
+        } else {
+            out.push_str(&format!(" {tile} "));
+        }
+    }
+
+    fn push_square(&self, out: &mut String, pos: usize) {
+        let (label, style) = if pos == TOTAL_SIZE / 2 {
+            (" * ", DOUBLE_WORD_SQUARE)
+        } else {
+            match TILE_BONUSES[pos] {
+                TRIPLE_WORD => ("TW ", TRIPLE_WORD_SQUARE),
+                DOUBLE_WORD => ("DW ", DOUBLE_WORD_SQUARE),
+                TRIPLE_LETTER => ("TL ", TRIPLE_LETTER_SQUARE),
+                DOUBLE_LETTER => ("DL ", DOUBLE_LETTER_SQUARE),
+                QUADRUPLE_WORD => ("QW ", QUADRUPLE_SQUARE),
+                QUADRUPLE_LETTER => ("QL ", QUADRUPLE_SQUARE),
+                _ => (" . ", NORMAL_SQUARE),
+            }
+        };
+
+        if self.color {
+            out.push_str(&format!("{style}{label}{RESET}"));
+        } else {
+            out.push_str(label);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{BoardPosition, EMPTY_TILE, RACK_SIZE};
+
+    fn board_with_cat() -> (Board, CrosswordMove) {
+        let mut board = Board::new();
+        let mut positions: [BoardPosition; RACK_SIZE] = [0; RACK_SIZE];
+        positions[..3].copy_from_slice(&[111, 112, 113]);
+        let cat = CrosswordMove::from_arrays(
+            [
+                'C', 'a', 'T', EMPTY_TILE, EMPTY_TILE, EMPTY_TILE, EMPTY_TILE,
+            ],
+            positions,
+            3,
+        );
+        board.make_move(&cat);
+        (board, cat)
+    }
+
+    #[test]
+    fn plain_rendering_has_coordinates_and_premiums() {
+        let board = Board::new();
+        let out = TerminalRenderer::plain().render(&board, None);
+        let lines: Vec<&str> = out.lines().collect();
+
+        assert_eq!(lines.len(), BOARD_SIZE + 1);
+        assert!(lines[0].starts_with("    A  B  C"));
+        assert!(lines[0].trim_end().ends_with('O'));
+        assert!(lines[1].starts_with(" 1 TW  .  . DL "));
+        assert!(lines[8].contains(" * "));
+        assert!(lines[15].starts_with("15 TW "));
+        assert!(out.is_ascii());
+    }
+
+    #[test]
+    fn plain_rendering_marks_last_move_and_blanks() {
+        let (board, cat) = board_with_cat();
+
+        let without = TerminalRenderer::plain().render(&board, None);
+        assert!(without.lines().nth(8).unwrap().contains(" C  a  T "));
+
+        let with = TerminalRenderer::plain().render(&board, Some(&cat));
+        assert!(with.lines().nth(8).unwrap().contains("[C][a][T]"));
+    }
+
+    #[test]
+    fn colored_rendering_uses_ansi_styles() {
+        let (board, cat) = board_with_cat();
+        let out = TerminalRenderer::colored().render(&board, Some(&cat));
+
+        assert!(out.contains(&format!("{LAST_MOVE_BLANK} a {RESET}")));
+        assert!(out.contains(&format!("{TRIPLE_WORD_SQUARE}TW {RESET}")));
+        assert!(
+            !TerminalRenderer::plain()
+                .render(&board, None)
+                .contains('\x1b')
+        );
+    }
+}